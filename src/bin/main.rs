@@ -1,53 +1,654 @@
-use anyhow::Context;
-use clap::Parser as CliParser;
-use quake_log_parser::{error::Result, parser::Parser, report::LogReport};
+use anyhow::{bail, Context};
+use clap::{ArgAction, Parser as CliParser, Subcommand, ValueEnum};
+use indexmap::IndexMap;
+use quake_log_parser::{
+    error::Result,
+    game::{Dialect, Game, KillCount, PlayerName, Timestamp},
+    parser::{
+        checkpoint::Checkpoint,
+        input::{sort_by_rotation, Decoder, LogFiles},
+        Parser,
+    },
+    report::{
+        charts::Chart,
+        highlights::HighlightsConfig,
+        ranking::RankingKey,
+        rating::{EloConfig, Glicko2Config, RatingSystem},
+        timeline::{TimelineConfig, TimelineSampling},
+        totals::{AliasGroups, IdentityKey},
+        render::Format,
+        stream::JsonReportWriter,
+        GameReport,
+        LogReport,
+        LogReportBuilder,
+        ReportConfig,
+    },
+};
+#[cfg(feature = "parallel")]
+use quake_log_parser::parser::parallel::ParallelParser;
 use simplelog::{Config, WriteLogger};
 use std::{
-    fs::{File, OpenOptions},
-    io,
-    path::PathBuf,
+    env,
+    fs::{self, File, OpenOptions},
+    io::{self, BufRead, BufWriter, IsTerminal, Read, Write},
+    path::{Path, PathBuf},
     process::exit,
 };
 
+/// Path standing for the standard input or output.
+const STANDARD_STREAM_PATH: &str = "-";
+
 /// Program helper: parses a Quake III: Arena log file and prints a grouped data
 /// JSON object.
 #[derive(Debug, Clone, CliParser)]
+#[command(subcommand_precedence_over_arg = true)]
 struct Arguments {
-    /// Path to Quake III: Arena log file.
+    /// Paths to Quake III: Arena log files, read in the given order as a
+    /// single log, e.g. rotated files from the oldest to the newest. `-` reads
+    /// the log from the standard input.
     #[arg(default_value = "qgames.log")]
-    quake_log: PathBuf,
-    /// Path to the log generated by this script for troubleshooting.
-    #[arg(short = 'l', long = "script-log", default_value = "script.log")]
-    script_log: PathBuf,
+    quake_logs: Vec<PathBuf>,
+    /// Glob pattern matching the log files to be read as a single log, e.g.
+    /// `qgames.log*`. Rotated files are read from the oldest to the newest.
+    #[arg(long = "glob", conflicts_with = "quake_logs")]
+    glob: Option<String>,
+    /// Keeps reading a single live log after its end, like `tail -F`, and
+    /// prints the report of each game as a JSON line as soon as it finishes.
+    #[arg(short = 'f', long = "follow", conflicts_with = "glob")]
+    follow: bool,
+    /// Path to the file the report is written to, atomically replacing it.
+    /// By default, or if `-`, the report is written to the standard output.
+    #[arg(short = 'o', long = "output", conflicts_with = "follow")]
+    output: Option<PathBuf>,
+    /// Format of the report. Live logs are always reported as
+    /// newline-delimited JSON.
+    #[arg(
+        long = "format",
+        value_enum,
+        default_value_t = OutputFormat::Json,
+        conflicts_with = "follow"
+    )]
+    format: OutputFormat,
+    /// When scoreboards are colored with ANSI escape codes. By default, only
+    /// if written to a terminal and `NO_COLOR` is not set.
+    #[arg(long = "color", value_enum, default_value_t = ColorChoice::Auto)]
+    color: ColorChoice,
+    /// Parses a single plain log in parallel, splitting it at game
+    /// boundaries. Meant for huge logs.
+    #[cfg(feature = "parallel")]
+    #[arg(
+        long = "parallel",
+        conflicts_with_all = ["glob", "follow", "checkpoint"]
+    )]
+    parallel: bool,
+    /// Path to a JSON checkpoint of a single plain log. If it exists, parsing
+    /// resumes from it and only new games are reported; the unfinished game
+    /// at the end of the log is saved into it for the next run.
+    #[arg(long = "checkpoint", conflicts_with_all = ["glob", "follow"])]
+    checkpoint: Option<PathBuf>,
+    /// The game or mod that wrote the log. By default, it is detected for each
+    /// game.
+    #[arg(long = "dialect", value_enum)]
+    dialect: Option<LogDialect>,
+    /// Path to a file the messages of this script are appended to, for
+    /// troubleshooting. By default, they are written to the standard error.
+    #[arg(short = 'l', long = "script-log")]
+    script_log: Option<PathBuf>,
+    /// Makes messages of this script more verbose; can be repeated.
+    #[arg(short = 'v', long = "verbose", action = ArgAction::Count)]
+    verbose: u8,
+    /// Makes messages of this script quieter; can be repeated.
+    #[arg(short = 'q', long = "quiet", action = ArgAction::Count)]
+    quiet: u8,
+    /// How players are identified across games in the player totals.
+    #[arg(long = "identity", value_enum, default_value_t = Identity::Name)]
+    identity: Identity,
+    /// Path to a JSON object mapping canonical player names to lists of
+    /// aliases. Players are then identified by alias groups.
+    #[arg(long = "aliases", conflicts_with = "identity")]
+    aliases: Option<PathBuf>,
+    /// Comma-separated keys by which players are ranked, the first one being
+    /// the primary key and the following ones breaking ties.
+    #[arg(
+        long = "rank-by",
+        value_enum,
+        value_delimiter = ',',
+        default_value = "score"
+    )]
+    rank_by: Vec<RankBy>,
+    /// Rating system used to rate players across games, in log order.
+    #[arg(long = "ratings", value_enum)]
+    ratings: Option<RatingKind>,
+    /// Maximum seconds between two kills of a player for them to be part of
+    /// the same multi-kill.
+    #[arg(long = "multi-kill-window", default_value_t = 3)]
+    multi_kill_window: Timestamp,
+    /// Minimum length of a killing streak for its end to be a shutdown.
+    #[arg(long = "min-shutdown-streak", default_value_t = 3)]
+    min_shutdown_streak: KillCount,
+    /// Includes the score-over-time timeline of each game, sampled at every
+    /// kill unless an interval is given.
+    #[arg(long = "timeline")]
+    timeline: bool,
+    /// Seconds between samples of the score timeline.
+    #[arg(long = "timeline-interval", requires = "timeline")]
+    timeline_interval: Option<Timestamp>,
+    /// Minimum deficit a player or team must recover from for taking the lead
+    /// to count as a comeback.
+    #[arg(long = "comeback-deficit", default_value_t = 3)]
+    comeback_deficit: KillCount,
+    /// What to do instead of writing the report.
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+/// Subcommands of the script.
+#[derive(Debug, Clone, Subcommand)]
+enum Command {
+    /// Draws SVG charts of each game instead of writing the report: scores
+    /// over time, kills by weapon and a heatmap of the kill matrix.
+    Charts {
+        /// Directory the charts are written to, named after the game and the
+        /// chart, e.g. `game_1-timeline.svg`.
+        #[arg(short = 'd', long = "directory", default_value = ".")]
+        directory: PathBuf,
+    },
+}
+
+/// Report format, as given in the command line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    /// Indented JSON.
+    Json,
+    /// JSON without whitespace.
+    JsonCompact,
+    /// Newline-delimited JSON, one game per line.
+    Ndjson,
+    /// YAML.
+    #[cfg(feature = "yaml")]
+    Yaml,
+    /// CSV, one record per player per game.
+    #[cfg(feature = "csv")]
+    Csv,
+    /// Markdown tables.
+    #[cfg(feature = "markdown")]
+    Markdown,
+    /// Scoreboards aligned for terminals, one per game.
+    Scoreboard,
+    /// A self-contained HTML page.
+    #[cfg(feature = "html")]
+    Html,
+}
+
+/// When to color output, as given in the command line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ColorChoice {
+    /// Only if written to a terminal and `NO_COLOR` is not set.
+    Auto,
+    /// Always.
+    Always,
+    /// Never.
+    Never,
+}
+
+/// Where and how the report is written.
+#[derive(Debug, Clone, Copy)]
+struct Output<'args> {
+    /// Path to the output file, or `None` for the standard output.
+    path: Option<&'args Path>,
+    /// Format of the report.
+    format: Format,
+}
+
+impl Output<'_> {
+    /// Writes the report of the whole log made of the given games. JSON
+    /// formats are written game by game as the games are yielded, without
+    /// holding all game reports in memory; other formats need the whole
+    /// report first.
+    fn write_games<I>(self, game_iter: I, config: &ReportConfig) -> Result<()>
+    where
+        I: IntoIterator<Item = Result<Game>>,
+    {
+        write_output(self.path, |writer| match self.format {
+            Format::JsonPretty => {
+                let mut json = JsonReportWriter::pretty(writer, config)?;
+                json.add_games(game_iter)?;
+                json.finish()?;
+                Ok(())
+            },
+            Format::JsonCompact => {
+                let mut json = JsonReportWriter::compact(writer, config)?;
+                json.add_games(game_iter)?;
+                json.finish()?;
+                Ok(())
+            },
+            Format::Ndjson => {
+                let mut builder = LogReportBuilder::new(config);
+                for result in game_iter {
+                    let (game_id, game_report) =
+                        builder.report_game(&result?)?;
+                    let entry = IndexMap::from([(game_id, game_report)]);
+                    Format::Ndjson.render(&entry, &mut *writer)?;
+                }
+                Ok(())
+            },
+            #[allow(unreachable_patterns)]
+            format => {
+                let report = LogReport::generate_with(game_iter, config)?;
+                format.render(&report, writer)
+            },
+        })
+    }
+}
+
+/// Log dialect, as given in the command line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum LogDialect {
+    /// Quake III: Arena.
+    Baseq3,
+    /// Quake III: Team Arena.
+    Missionpack,
+    /// OpenArena.
+    Openarena,
+    /// Urban Terror 4.x.
+    Urbanterror,
+}
+
+/// Player identity key, as given in the command line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Identity {
+    /// The exact name, including color codes.
+    Name,
+    /// The name without color codes.
+    StrippedName,
+}
+
+/// Ranking key, as given in the command line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum RankBy {
+    /// Score in terms of killing.
+    Score,
+    /// Kills of other players.
+    Frags,
+    /// Ratio between frags and deaths.
+    Kd,
+}
+
+/// Rating system, as given in the command line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum RatingKind {
+    /// Elo rating system.
+    Elo,
+    /// Glicko-2 rating system.
+    Glicko2,
+}
+
+/// Builds the report configuration from command line arguments.
+fn report_config(args: &Arguments) -> Result<ReportConfig> {
+    let identity = match &args.aliases {
+        Some(path) => {
+            let file = File::open(path).with_context(|| {
+                format!("Failed to open {}", path.display())
+            })?;
+            let groups: IndexMap<PlayerName, Vec<PlayerName>> =
+                serde_json::from_reader(io::BufReader::new(file))
+                    .with_context(|| {
+                        format!("Invalid aliases in {}", path.display())
+                    })?;
+            let mut alias_groups = AliasGroups::new();
+            for (canonical, aliases) in &groups {
+                alias_groups.insert_group(canonical, aliases);
+            }
+            IdentityKey::Alias(alias_groups)
+        },
+        None => match args.identity {
+            Identity::Name => IdentityKey::Name,
+            Identity::StrippedName => IdentityKey::StrippedName,
+        },
+    };
+    let ranking_keys = args
+        .rank_by
+        .iter()
+        .map(|key| match key {
+            RankBy::Score => RankingKey::Score,
+            RankBy::Frags => RankingKey::Frags,
+            RankBy::Kd => RankingKey::KillDeathRatio,
+        })
+        .collect();
+    let rating = args.ratings.map(|kind| match kind {
+        RatingKind::Elo => RatingSystem::Elo(EloConfig::default()),
+        RatingKind::Glicko2 => RatingSystem::Glicko2(Glicko2Config::default()),
+    });
+    let highlights = HighlightsConfig {
+        multi_kill_window: args.multi_kill_window,
+        min_shutdown_streak: args.min_shutdown_streak,
+    };
+    let draws_charts = matches!(args.command, Some(Command::Charts { .. }));
+    let timeline = (args.timeline || draws_charts).then_some(TimelineConfig {
+        sampling: match args.timeline_interval {
+            Some(interval) => TimelineSampling::Interval(interval),
+            None => TimelineSampling::EveryKill,
+        },
+        comeback_deficit: args.comeback_deficit,
+    });
+    Ok(ReportConfig {
+        identity,
+        ranking_keys,
+        rating,
+        highlights,
+        timeline,
+        games_before: 0,
+    })
+}
+
+/// Tests whether the given path stands for the standard input or output.
+fn is_standard_stream(path: &Path) -> bool {
+    path == Path::new(STANDARD_STREAM_PATH)
+}
+
+/// Sets up the logger of this script, writing to the script log file if
+/// given, and to the standard error otherwise.
+fn setup_logger(args: &Arguments) -> Result<()> {
+    const LEVELS: [log::LevelFilter; 6] = [
+        log::LevelFilter::Off,
+        log::LevelFilter::Error,
+        log::LevelFilter::Warn,
+        log::LevelFilter::Info,
+        log::LevelFilter::Debug,
+        log::LevelFilter::Trace,
+    ];
+    let level_index = (2 + usize::from(args.verbose))
+        .saturating_sub(usize::from(args.quiet))
+        .min(LEVELS.len() - 1);
+    let level = LEVELS[level_index];
+
+    let result = match &args.script_log {
+        Some(path) => {
+            #[allow(clippy::ineffective_open_options)]
+            let script_log_file = OpenOptions::new()
+                .write(true)
+                .append(true)
+                .create(true)
+                .open(path)
+                .with_context(|| format!("Failed to open {}", path.display()))?;
+            WriteLogger::init(level, Config::default(), script_log_file)
+        },
+        None => WriteLogger::init(level, Config::default(), io::stderr()),
+    };
+    result.context("Failed to setup the application logger")
+}
+
+/// The single log file given in the arguments, failing with the given reason
+/// if there are more or if it is the standard input.
+fn single_log_file<'args>(
+    args: &'args Arguments,
+    reason: &str,
+) -> Result<&'args Path> {
+    match args.quake_logs.as_slice() {
+        [quake_log] if !is_standard_stream(quake_log) => Ok(quake_log),
+        _ => bail!("A single log file is required {}", reason),
+    }
+}
+
+/// Opens the log to be read, either the standard input or the log files, in
+/// reading order. Compressed logs are decompressed.
+fn open_log(args: &Arguments) -> Result<Box<dyn Read>> {
+    if !args.quake_logs.iter().any(|path| is_standard_stream(path)) {
+        return Ok(Box::new(LogFiles::new(log_paths(args)?)));
+    }
+    if args.quake_logs.len() > 1 || args.glob.is_some() {
+        bail!("The standard input cannot be read along with log files");
+    }
+    Ok(Box::new(Decoder::new(io::stdin())?))
+}
+
+/// Lists the log files to be read, in reading order.
+fn log_paths(args: &Arguments) -> Result<Vec<PathBuf>> {
+    let Some(pattern) = &args.glob else {
+        return Ok(args.quake_logs.clone());
+    };
+    let mut paths = glob::glob(pattern)
+        .with_context(|| format!("Invalid glob pattern {}", pattern))?
+        .collect::<Result<Vec<_>, _>>()?;
+    if paths.is_empty() {
+        bail!("No log file matches {}", pattern);
+    }
+    sort_by_rotation(&mut paths);
+    Ok(paths)
+}
+
+/// Follows a live log, printing the report of each game as a JSON line as
+/// soon as the game finishes.
+fn follow_log<R>(parser: Parser<R>, config: &ReportConfig) -> Result<()>
+where
+    R: BufRead,
+{
+    let mut stdout = io::stdout().lock();
+    for (i, result) in parser.enumerate() {
+        let game_report = GameReport::generate_with(&result?, config)?;
+        let game_id = format!("game_{}", i + 1);
+        let entry = IndexMap::from([(game_id, game_report)]);
+        Format::Ndjson.render(&entry, &mut stdout)?;
+        stdout.flush()?;
+    }
+    Ok(())
+}
+
+/// Draws the charts of each game as SVG files in the given directory, as soon
+/// as the game is parsed.
+fn draw_charts<I>(
+    game_iter: I,
+    config: &ReportConfig,
+    directory: &Path,
+) -> Result<()>
+where
+    I: IntoIterator<Item = Result<Game>>,
+{
+    fs::create_dir_all(directory).with_context(|| {
+        format!("Failed to create {}", directory.display())
+    })?;
+    let mut builder = LogReportBuilder::new(config);
+    for result in game_iter {
+        let (game_id, game_report) = builder.report_game(&result?)?;
+        for &chart in Chart::ALL {
+            let file_name = format!("{}-{}.svg", game_id, chart.name());
+            write_output(Some(&directory.join(file_name)), |writer| {
+                chart.render(&game_report, writer)
+            })?;
+        }
+    }
+    Ok(())
+}
+
+/// Parses new games of a log since its last checkpoint, if any, printing
+/// their report and saving a new checkpoint.
+fn parse_checkpointed(
+    quake_log: &Path,
+    checkpoint_path: &Path,
+    dialect: Option<Dialect>,
+    mut config: ReportConfig,
+    output: Output,
+) -> Result<()> {
+    let quake_file = File::open(quake_log).with_context(|| {
+        format!("Failed to open {}", quake_log.display())
+    })?;
+    let mut parser = match fs::read(checkpoint_path) {
+        Ok(contents) => {
+            let checkpoint: Checkpoint = serde_json::from_slice(&contents)
+                .with_context(|| {
+                    format!("Invalid checkpoint {}", checkpoint_path.display())
+                })?;
+            Parser::resume_file(quake_file, checkpoint)?
+        },
+        Err(error) if error.kind() == io::ErrorKind::NotFound => {
+            Parser::new(quake_file).incremental()
+        },
+        Err(error) => Err(error).with_context(|| {
+            format!("Failed to read {}", checkpoint_path.display())
+        })?,
+    };
+    if let Some(dialect) = dialect {
+        parser = parser.with_dialect(dialect);
+    }
+    config.games_before = parser.checkpoint().games;
+
+    output.write_games(parser.by_ref(), &config)?;
+
+    write_output(Some(checkpoint_path), |writer| {
+        serde_json::to_writer(writer, &parser.file_checkpoint()?)?;
+        Ok(())
+    })
 }
 
 /// Main function proxy that returns errors instead of exiting.
 fn try_main() -> Result<()> {
     let args = Arguments::parse();
+    setup_logger(&args)?;
 
-    let script_log_file = OpenOptions::new()
-        .read(false)
-        .write(true)
-        .append(true)
-        .create(true)
-        .open(&args.script_log)?;
+    let dialect = args.dialect.map(|dialect| match dialect {
+        LogDialect::Baseq3 => Dialect::Baseq3,
+        LogDialect::Missionpack => Dialect::MissionPack,
+        LogDialect::Openarena => Dialect::OpenArena,
+        LogDialect::Urbanterror => Dialect::UrbanTerror,
+    });
+    let config = report_config(&args)?;
+    let output = Output {
+        path: args.output.as_deref().filter(|path| !is_standard_stream(path)),
+        format: match args.format {
+            OutputFormat::Json => Format::JsonPretty,
+            OutputFormat::JsonCompact => Format::JsonCompact,
+            OutputFormat::Ndjson => Format::Ndjson,
+            #[cfg(feature = "yaml")]
+            OutputFormat::Yaml => Format::Yaml,
+            #[cfg(feature = "csv")]
+            OutputFormat::Csv => Format::Csv,
+            #[cfg(feature = "markdown")]
+            OutputFormat::Markdown => Format::Markdown,
+            OutputFormat::Scoreboard => Format::Scoreboard {
+                color: use_color(&args),
+            },
+            #[cfg(feature = "html")]
+            OutputFormat::Html => Format::Html,
+        },
+    };
 
-    WriteLogger::init(
-        log::LevelFilter::Warn,
-        Config::default(),
-        script_log_file,
-    )
-    .context("Failed to setup the application logger")?;
+    if let Some(Command::Charts { directory }) = &args.command {
+        if args.follow || args.checkpoint.is_some() {
+            bail!("Charts cannot be drawn while following or checkpointing");
+        }
+        let parser = with_dialect(Parser::new(open_log(&args)?), dialect);
+        return draw_charts(parser, &config, directory);
+    }
 
-    let quake_file = File::open(&args.quake_log).with_context(|| {
-        format!("Failed to open {}", args.quake_log.display())
-    })?;
-    let parser = Parser::new(quake_file);
-    let report = LogReport::generate(parser)?;
-    serde_json::to_writer_pretty(io::stdout(), &report)?;
-    println!();
+    if args.follow {
+        return match args.quake_logs.as_slice() {
+            [quake_log] if is_standard_stream(quake_log) => {
+                let parser = Parser::new(Decoder::new(io::stdin())?);
+                follow_log(with_dialect(parser, dialect), &config)
+            },
+            _ => {
+                let quake_log = single_log_file(&args, "to be followed")?;
+                let parser = Parser::follow(quake_log)?;
+                follow_log(with_dialect(parser, dialect), &config)
+            },
+        };
+    }
 
-    Ok(())
+    if let Some(checkpoint_path) = &args.checkpoint {
+        let quake_log = single_log_file(&args, "for checkpoints")?;
+        return parse_checkpointed(
+            quake_log,
+            checkpoint_path,
+            dialect,
+            config,
+            output,
+        );
+    }
+
+    #[cfg(feature = "parallel")]
+    if args.parallel {
+        let quake_log = single_log_file(&args, "to be parsed in parallel")?;
+        let mut parser = ParallelParser::new();
+        if let Some(dialect) = dialect {
+            parser = parser.with_dialect(dialect);
+        }
+        let games = parser.parse_file(quake_log)?.into_iter().map(Ok);
+        return output.write_games(games, &config);
+    }
+
+    let parser = with_dialect(Parser::new(open_log(&args)?), dialect);
+    output.write_games(parser, &config)
+}
+
+/// Tells whether scoreboards are colored, according to the color choice and,
+/// if automatic, to where they are written.
+fn use_color(args: &Arguments) -> bool {
+    match args.color {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto => {
+            let to_stdout =
+                args.output.as_deref().is_none_or(is_standard_stream);
+            to_stdout
+                && io::stdout().is_terminal()
+                && env::var_os("NO_COLOR").is_none_or(|value| value.is_empty())
+        },
+    }
+}
+
+/// Sets the dialect of the parser, if one was given.
+fn with_dialect<R>(parser: Parser<R>, dialect: Option<Dialect>) -> Parser<R>
+where
+    R: BufRead,
+{
+    match dialect {
+        Some(dialect) => parser.with_dialect(dialect),
+        None => parser,
+    }
+}
+
+/// Writes to the file at the given path atomically, by writing to a temporary
+/// file first and then renaming it. Writes to the standard output if there is
+/// no path, and directly to the path if it is not a regular file (e.g. a
+/// device or a pipe).
+fn write_output<F>(path: Option<&Path>, write: F) -> Result<()>
+where
+    F: FnOnce(&mut dyn Write) -> Result<()>,
+{
+    let Some(path) = path else {
+        let mut stdout = io::stdout().lock();
+        write(&mut stdout)?;
+        return Ok(stdout.flush()?);
+    };
+
+    let is_special = fs::metadata(path)
+        .is_ok_and(|metadata| !metadata.file_type().is_file());
+    if is_special {
+        let mut writer = OpenOptions::new()
+            .write(true)
+            .open(path)
+            .map(BufWriter::new)
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+        write(&mut writer)?;
+        return Ok(writer.flush()?);
+    }
+
+    let mut temp_name = path.file_name().unwrap_or_default().to_os_string();
+    temp_name.push(".tmp");
+    let temp_path = path.with_file_name(temp_name);
+    let write_temp = || -> Result<()> {
+        let mut writer = BufWriter::new(File::create(&temp_path)?);
+        write(&mut writer)?;
+        let file = writer.into_inner().map_err(|error| error.into_error())?;
+        file.sync_all()?;
+        fs::rename(&temp_path, path)?;
+        Ok(())
+    };
+    let result = write_temp();
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result.with_context(|| format!("Failed to write {}", path.display()))
 }
 
 /// Executable main function.
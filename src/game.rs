@@ -1,8 +1,19 @@
 //! This module exposes game datatype and related items common to all other
 //! modules.
 
-use indexmap::IndexSet;
-use std::{collections::HashMap, sync::OnceLock};
+use std::{collections::HashMap, fmt};
+
+pub use dialect::Dialect;
+pub use means::{MeansOfDeath, ModTable};
+pub use weapon::{Weapon, WeaponCategory};
+
+pub mod dialect;
+pub mod means;
+pub mod replay;
+pub mod weapon;
+
+#[cfg(test)]
+mod test;
 
 /// Player ID in the log file. This integer bit size should be Ok for an old
 /// game.
@@ -18,31 +29,76 @@ pub type KillCount = i64;
 /// the future it could be a reference-counted string or an interned string.
 pub type PlayerName = String;
 
-/// Means of Death (MOD) as referenced by the log file. It could be an `enum`,
-/// but `enum` is not necessary in this case. A simple string literal is enough
-/// and it is cheap to copy.
-pub type MeansOfDeath = &'static str;
-
-/// Returns a set with all valid MODs. This set is created only in the first
-/// call.
-pub fn all_means_of_death() -> &'static IndexSet<MeansOfDeath> {
-    static MOD_CELL: OnceLock<IndexSet<MeansOfDeath>> = OnceLock::new();
-    MOD_CELL.get_or_init(|| {
-        IndexSet::from([
-            "MOD_UNKNOWN", "MOD_SHOTGUN", "MOD_GAUNTLET", "MOD_MACHINEGUN",
-            "MOD_GRENADE", "MOD_GRENADE_SPLASH", "MOD_ROCKET",
-            "MOD_ROCKET_SPLASH", "MOD_PLASMA", "MOD_PLASMA_SPLASH",
-            "MOD_RAILGUN", "MOD_LIGHTNING", "MOD_BFG", "MOD_BFG_SPLASH",
-            "MOD_WATER", "MOD_SLIME", "MOD_LAVA", "MOD_CRUSH", "MOD_TELEFRAG",
-            "MOD_FALLING", "MOD_SUICIDE", "MOD_TARGET_LASER",
-            "MOD_TRIGGER_HURT", "MOD_NAIL", "MOD_CHAINGUN",
-            "MOD_PROXIMITY_MINE", "MOD_KAMIKAZE", "MOD_JUICED", "MOD_GRAPPLE",
-        ])
+/// Time of an event in seconds, as printed in the `MM:SS` prefix of a log
+/// line. Quake prints the time elapsed since the server started the current
+/// map, so it is meaningful within a single game.
+pub type Timestamp = u64;
+
+/// Formats a time or duration in seconds as `M:SS`, like the time prefix of
+/// log lines.
+pub fn format_timestamp(time: Timestamp) -> String {
+    format!("{}:{:02}", time / 60, time % 60)
+}
+
+/// Quake's color escape character: `^` followed by a character (other than
+/// `^` itself) changes the color of the following text, as in `^1Red`.
+pub const COLOR_ESCAPE: char = '^';
+
+/// A piece of text with Quake color codes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorPiece {
+    /// A color code, with the index of its color in the Quake palette, from
+    /// `0` (black) to `7` (white).
+    Color(usize),
+    /// A character displayed as is.
+    Char(char),
+}
+
+/// Splits the given text into color codes and displayed characters. Like
+/// Quake, only the last three bits of a color code count, so `^8` is black
+/// again and `^9` is red.
+pub fn color_pieces(text: &str) -> impl Iterator<Item = ColorPiece> + '_ {
+    let mut chars = text.chars().peekable();
+    std::iter::from_fn(move || {
+        let ch = chars.next()?;
+        match chars.peek() {
+            Some(&next) if ch == COLOR_ESCAPE && next != COLOR_ESCAPE => {
+                chars.next();
+                let index = (next as usize).wrapping_sub('0' as usize) & 7;
+                Some(ColorPiece::Color(index))
+            },
+            _ => Some(ColorPiece::Char(ch)),
+        }
     })
 }
 
+/// Returns the given player name without Quake color codes, e.g.
+/// `^1Isga^7lamido` becomes `Isgalamido`. Leading and trailing whitespace is
+/// trimmed as well.
+pub fn strip_color_codes(name: &str) -> PlayerName {
+    let stripped: PlayerName = color_pieces(name)
+        .filter_map(|piece| match piece {
+            ColorPiece::Char(ch) => Some(ch),
+            ColorPiece::Color(_) => None,
+        })
+        .collect();
+    PlayerName::from(stripped.trim())
+}
+
 /// The agent that kills another agent in the `Kill` event.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
 pub enum Killer {
     /// The world is the killer, e.g. the target died as an accident.
     World,
@@ -51,7 +107,7 @@ pub enum Killer {
 }
 
 /// A `Kill` event as read by the log file.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct Kill {
     /// The killer agent, player or world.
     pub killer: Killer,
@@ -59,13 +115,384 @@ pub struct Kill {
     pub target: PlayerId,
     /// The way this killing happened.
     pub means: MeansOfDeath,
+    /// When this killing happened.
+    pub time: Timestamp,
+}
+
+/// Game type as set by the `g_gametype` server variable in `InitGame`.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Default,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum GameType {
+    /// Every player for themselves (`0`).
+    #[default]
+    FreeForAll,
+    /// One against one (`1`).
+    Tournament,
+    /// Single player against bots (`2`).
+    SinglePlayer,
+    /// Team deathmatch (`3`).
+    TeamDeathmatch,
+    /// Capture the flag (`4`).
+    CaptureTheFlag,
+    /// One flag CTF, from `MISSIONPACK` (`5`).
+    OneFlag,
+    /// Overload, from `MISSIONPACK` (`6`).
+    Obelisk,
+    /// Harvester, from `MISSIONPACK` (`7`).
+    Harvester,
+    /// A game type not known by this library, with its `g_gametype` value.
+    Other(u32),
+}
+
+impl GameType {
+    /// Converts the value of `g_gametype` into a game type.
+    pub fn from_code(code: u32) -> Self {
+        match code {
+            0 => Self::FreeForAll,
+            1 => Self::Tournament,
+            2 => Self::SinglePlayer,
+            3 => Self::TeamDeathmatch,
+            4 => Self::CaptureTheFlag,
+            5 => Self::OneFlag,
+            6 => Self::Obelisk,
+            7 => Self::Harvester,
+            _ => Self::Other(code),
+        }
+    }
+
+    /// The name of this game type as serialized, e.g. `free_for_all`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::FreeForAll => "free_for_all",
+            Self::Tournament => "tournament",
+            Self::SinglePlayer => "single_player",
+            Self::TeamDeathmatch => "team_deathmatch",
+            Self::CaptureTheFlag => "capture_the_flag",
+            Self::OneFlag => "one_flag",
+            Self::Obelisk => "obelisk",
+            Self::Harvester => "harvester",
+            Self::Other(_) => "other",
+        }
+    }
+
+    /// Whether players are split in teams in this game type.
+    pub fn is_team_game(self) -> bool {
+        matches!(
+            self,
+            Self::TeamDeathmatch
+                | Self::CaptureTheFlag
+                | Self::OneFlag
+                | Self::Obelisk
+                | Self::Harvester
+        )
+    }
+}
+
+impl fmt::Display for GameType {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(self.name())
+    }
+}
+
+/// Team of a player as set by the `t` key in `ClientUserinfoChanged`.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Default,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Team {
+    /// No team, as in free for all games (`0`).
+    #[default]
+    Free,
+    /// Red team (`1`).
+    Red,
+    /// Blue team (`2`).
+    Blue,
+    /// Watching the game, not playing (`3`).
+    Spectator,
+}
+
+impl Team {
+    /// Converts the value of the `t` key into a team, if valid.
+    pub fn from_code(code: u32) -> Option<Self> {
+        match code {
+            0 => Some(Self::Free),
+            1 => Some(Self::Red),
+            2 => Some(Self::Blue),
+            3 => Some(Self::Spectator),
+            _ => None,
+        }
+    }
+
+    /// The name of this team as serialized, e.g. `red`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Free => "free",
+            Self::Red => "red",
+            Self::Blue => "blue",
+            Self::Spectator => "spectator",
+        }
+    }
+
+    /// The opposing team of a red or blue team, `None` for other teams.
+    pub fn opponent(self) -> Option<Self> {
+        match self {
+            Self::Red => Some(Self::Blue),
+            Self::Blue => Some(Self::Red),
+            Self::Free | Self::Spectator => None,
+        }
+    }
+}
+
+impl fmt::Display for Team {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(self.name())
+    }
+}
+
+/// Count of games, e.g. games played by a player or games read from a log.
+pub type GameCount = u64;
+
+/// Count of awards gained by a player.
+pub type AwardCount = u64;
+
+/// An award (medal) gained by a player, as in the `Award` event.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Award {
+    /// A kill with the gauntlet.
+    Gauntlet,
+    /// Two kills within two seconds.
+    Excellent,
+    /// Two consecutive railgun hits.
+    Impressive,
+    /// Killing an enemy in the vicinity of the team's flag (CTF).
+    Defend,
+    /// Capturing the enemy flag (CTF).
+    Capture,
+    /// Helping a teammate to capture the flag (CTF).
+    Assist,
+}
+
+impl Award {
+    /// All known awards.
+    pub const ALL: &'static [Self] = &[
+        Self::Gauntlet,
+        Self::Excellent,
+        Self::Impressive,
+        Self::Defend,
+        Self::Capture,
+        Self::Assist,
+    ];
+
+    /// Converts an award name as printed in the log, such as `IMPRESSIVE`,
+    /// into an award, if known.
+    pub fn from_log_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_uppercase().as_str() {
+            "GAUNTLET" => Some(Self::Gauntlet),
+            "EXCELLENT" => Some(Self::Excellent),
+            "IMPRESSIVE" => Some(Self::Impressive),
+            "DEFEND" | "DEFENCE" | "DEFENSE" => Some(Self::Defend),
+            "CAPTURE" => Some(Self::Capture),
+            "ASSIST" => Some(Self::Assist),
+            _ => None,
+        }
+    }
+}
+
+/// An award gained by a player at some point of a game.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct GainedAward {
+    /// The award itself.
+    pub award: Award,
+    /// When it was gained.
+    pub time: Timestamp,
+}
+
+/// What happened to a flag in a capture the flag game.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum FlagAction {
+    /// A player took the enemy flag, either from its base or dropped.
+    Pickup,
+    /// The carrier brought the enemy flag to their own base.
+    Capture,
+    /// A player returned their own dropped flag to its base.
+    Return,
+    /// The carrier dropped the flag, killed by the given agent, if known.
+    Drop { killer: Option<Killer> },
+}
+
+/// An action on a flag in a capture the flag game, derived from `Item` events
+/// of team flags and from kills of flag carriers.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct FlagEvent {
+    /// The flag, identified by the team that owns it.
+    pub flag: Team,
+    /// What happened to the flag.
+    pub action: FlagAction,
+    /// The player who touched the flag, or the carrier who dropped it.
+    pub player: PlayerId,
+    /// When it happened.
+    pub time: Timestamp,
+}
+
+/// What happened in an event of a game.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GameEventKind {
+    /// The player with the given ID connected.
+    Connect { player: PlayerId },
+    /// The player with the given ID disconnected.
+    Disconnect { player: PlayerId },
+    /// The information of the player with the given ID changed, setting their
+    /// name and possibly their team.
+    PlayerInfoChanged {
+        player: PlayerId,
+        name: PlayerName,
+        team: Option<Team>,
+    },
+    /// A kill, given by its index in the game's kill list.
+    Kill { index: usize },
+}
+
+/// An event of a game that changes its state, such as the connected players
+/// and their scores.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct GameEvent {
+    /// What happened.
+    pub kind: GameEventKind,
+    /// When it happened.
+    pub time: Timestamp,
 }
 
 /// A game, a full match as read by the logs.
-#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    Default,
+    serde::Serialize,
+    serde::Deserialize,
+)]
 pub struct Game {
+    /// The type of this game, such as free for all or team deathmatch.
+    pub game_type: GameType,
+    /// The game or mod that wrote the log of this game.
+    pub dialect: Dialect,
+    /// Version of the server, as in the `version` server variable, if known.
+    pub version: Option<String>,
+    /// Name of the map, as in the `mapname` server variable, if known.
+    pub map: Option<String>,
+    /// When the game was initialized.
+    pub start_time: Timestamp,
+    /// When the game was shut down, if it was, rather than interrupted by
+    /// another game or by the end of the log.
+    pub end_time: Option<Timestamp>,
     /// Dictionary mapping player IDs to the names they last used in the game.
     pub players: HashMap<PlayerId, PlayerName>,
+    /// Dictionary mapping player IDs to the teams they were last in.
+    pub teams: HashMap<PlayerId, Team>,
     /// A list of `Kill` events in the order they happened.
     pub kills: Vec<Kill>,
+    /// Dictionary mapping player IDs to the awards they gained, in the order
+    /// they were gained.
+    pub awards: HashMap<PlayerId, Vec<GainedAward>>,
+    /// A list of actions on team flags in the order they happened.
+    pub flag_events: Vec<FlagEvent>,
+    /// A list of connections, disconnections, player information changes and
+    /// kills in the order they happened.
+    pub events: Vec<GameEvent>,
+}
+
+impl Game {
+    /// How long the game lasted, until it was shut down or, if it was not,
+    /// until its last known event.
+    pub fn duration(&self) -> Timestamp {
+        let end_time = self.end_time.unwrap_or_else(|| {
+            let kill_times = self.kills.iter().map(|kill| kill.time);
+            let event_times = self.events.iter().map(|event| event.time);
+            let flag_times = self.flag_events.iter().map(|event| event.time);
+            let award_times = self
+                .awards
+                .values()
+                .flatten()
+                .map(|gained_award| gained_award.time);
+            kill_times
+                .chain(event_times)
+                .chain(flag_times)
+                .chain(award_times)
+                .max()
+                .unwrap_or(self.start_time)
+        });
+        end_time.saturating_sub(self.start_time)
+    }
+
+    /// The player currently carrying the flag of the given team, if any,
+    /// according to the flag events so far.
+    pub fn flag_carrier(&self, flag: Team) -> Option<PlayerId> {
+        let last_event =
+            self.flag_events.iter().rev().find(|event| event.flag == flag)?;
+        match last_event.action {
+            FlagAction::Pickup => Some(last_event.player),
+            _ => None,
+        }
+    }
 }
@@ -0,0 +1,39 @@
+use super::Dialect;
+use crate::game::{GameType, MeansOfDeath, ModTable};
+
+#[test]
+fn mod_tables() {
+    assert_eq!(Dialect::Baseq3.mod_table(), Some(ModTable::Baseq3));
+    assert_eq!(Dialect::MissionPack.mod_table(), Some(ModTable::MissionPack));
+    assert_eq!(Dialect::OpenArena.mod_table(), Some(ModTable::MissionPack));
+    assert_eq!(Dialect::UrbanTerror.mod_table(), None);
+}
+
+#[test]
+fn urban_terror_means() {
+    let means = Dialect::UrbanTerror.known_means();
+    assert!(means.contains(&MeansOfDeath::Falling));
+    assert!(means.contains(&MeansOfDeath::from("UT_MOD_LR300")));
+    assert!(!means.contains(&MeansOfDeath::Railgun));
+}
+
+#[test]
+fn game_types() {
+    assert_eq!(Dialect::Baseq3.game_type(4), GameType::CaptureTheFlag);
+    assert_eq!(Dialect::UrbanTerror.game_type(7), GameType::CaptureTheFlag);
+    assert_eq!(Dialect::UrbanTerror.game_type(4), GameType::Other(4));
+}
+
+#[test]
+fn detect() {
+    let detect = |game_name, version| Dialect::detect(game_name, version);
+    assert_eq!(detect(Some("baseq3"), Some("ioq3 1.36")), Dialect::Baseq3);
+    assert_eq!(detect(Some("missionpack"), None), Dialect::MissionPack);
+    assert_eq!(detect(Some("baseoa"), None), Dialect::OpenArena);
+    assert_eq!(detect(None, Some("ioq3+oa 1.36_SVN")), Dialect::OpenArena);
+    assert_eq!(detect(Some("q3ut4"), None), Dialect::UrbanTerror);
+    assert_eq!(detect(Some("q3urt42"), None), Dialect::UrbanTerror);
+    let version = Some("ioq3 1.35 urt");
+    assert_eq!(detect(Some("Q3UT4"), version), Dialect::UrbanTerror);
+    assert_eq!(detect(None, None), Dialect::Baseq3);
+}
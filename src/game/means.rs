@@ -0,0 +1,270 @@
+//! This module exposes Means of Death (MOD), the way a player died, and the
+//! tables decoding MODs from their numeric codes.
+
+use std::{
+    collections::HashMap,
+    convert::Infallible,
+    fmt,
+    str::FromStr,
+    sync::OnceLock,
+};
+
+#[cfg(test)]
+mod test;
+
+/// Means of Death (MOD) as referenced by the log file, e.g. `MOD_ROCKET`.
+/// MODs not known by this library, such as those of mods or newer builds, are
+/// kept by name in `Other`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MeansOfDeath {
+    /// `MOD_UNKNOWN`.
+    Unknown,
+    /// `MOD_SHOTGUN`.
+    Shotgun,
+    /// `MOD_GAUNTLET`.
+    Gauntlet,
+    /// `MOD_MACHINEGUN`.
+    Machinegun,
+    /// `MOD_GRENADE`.
+    Grenade,
+    /// `MOD_GRENADE_SPLASH`.
+    GrenadeSplash,
+    /// `MOD_ROCKET`.
+    Rocket,
+    /// `MOD_ROCKET_SPLASH`.
+    RocketSplash,
+    /// `MOD_PLASMA`.
+    Plasma,
+    /// `MOD_PLASMA_SPLASH`.
+    PlasmaSplash,
+    /// `MOD_RAILGUN`.
+    Railgun,
+    /// `MOD_LIGHTNING`.
+    Lightning,
+    /// `MOD_BFG`.
+    Bfg,
+    /// `MOD_BFG_SPLASH`.
+    BfgSplash,
+    /// `MOD_WATER`.
+    Water,
+    /// `MOD_SLIME`.
+    Slime,
+    /// `MOD_LAVA`.
+    Lava,
+    /// `MOD_CRUSH`.
+    Crush,
+    /// `MOD_TELEFRAG`.
+    Telefrag,
+    /// `MOD_FALLING`.
+    Falling,
+    /// `MOD_SUICIDE`.
+    Suicide,
+    /// `MOD_TARGET_LASER`.
+    TargetLaser,
+    /// `MOD_TRIGGER_HURT`.
+    TriggerHurt,
+    /// `MOD_NAIL`, from `MISSIONPACK`.
+    Nail,
+    /// `MOD_CHAINGUN`, from `MISSIONPACK`.
+    Chaingun,
+    /// `MOD_PROXIMITY_MINE`, from `MISSIONPACK`.
+    ProximityMine,
+    /// `MOD_KAMIKAZE`, from `MISSIONPACK`.
+    Kamikaze,
+    /// `MOD_JUICED`, from `MISSIONPACK`.
+    Juiced,
+    /// `MOD_GRAPPLE`.
+    Grapple,
+    /// A MOD not known by this library, with its name as in the log.
+    Other(String),
+}
+
+impl MeansOfDeath {
+    /// All MODs known by this library, in the order of Quake's source code
+    /// with `MISSIONPACK` defined.
+    pub const ALL: &'static [Self] = &[
+        Self::Unknown,
+        Self::Shotgun,
+        Self::Gauntlet,
+        Self::Machinegun,
+        Self::Grenade,
+        Self::GrenadeSplash,
+        Self::Rocket,
+        Self::RocketSplash,
+        Self::Plasma,
+        Self::PlasmaSplash,
+        Self::Railgun,
+        Self::Lightning,
+        Self::Bfg,
+        Self::BfgSplash,
+        Self::Water,
+        Self::Slime,
+        Self::Lava,
+        Self::Crush,
+        Self::Telefrag,
+        Self::Falling,
+        Self::Suicide,
+        Self::TargetLaser,
+        Self::TriggerHurt,
+        Self::Nail,
+        Self::Chaingun,
+        Self::ProximityMine,
+        Self::Kamikaze,
+        Self::Juiced,
+        Self::Grapple,
+    ];
+
+    /// The name of this MOD as printed in the log, e.g. `MOD_ROCKET`.
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Unknown => "MOD_UNKNOWN",
+            Self::Shotgun => "MOD_SHOTGUN",
+            Self::Gauntlet => "MOD_GAUNTLET",
+            Self::Machinegun => "MOD_MACHINEGUN",
+            Self::Grenade => "MOD_GRENADE",
+            Self::GrenadeSplash => "MOD_GRENADE_SPLASH",
+            Self::Rocket => "MOD_ROCKET",
+            Self::RocketSplash => "MOD_ROCKET_SPLASH",
+            Self::Plasma => "MOD_PLASMA",
+            Self::PlasmaSplash => "MOD_PLASMA_SPLASH",
+            Self::Railgun => "MOD_RAILGUN",
+            Self::Lightning => "MOD_LIGHTNING",
+            Self::Bfg => "MOD_BFG",
+            Self::BfgSplash => "MOD_BFG_SPLASH",
+            Self::Water => "MOD_WATER",
+            Self::Slime => "MOD_SLIME",
+            Self::Lava => "MOD_LAVA",
+            Self::Crush => "MOD_CRUSH",
+            Self::Telefrag => "MOD_TELEFRAG",
+            Self::Falling => "MOD_FALLING",
+            Self::Suicide => "MOD_SUICIDE",
+            Self::TargetLaser => "MOD_TARGET_LASER",
+            Self::TriggerHurt => "MOD_TRIGGER_HURT",
+            Self::Nail => "MOD_NAIL",
+            Self::Chaingun => "MOD_CHAINGUN",
+            Self::ProximityMine => "MOD_PROXIMITY_MINE",
+            Self::Kamikaze => "MOD_KAMIKAZE",
+            Self::Juiced => "MOD_JUICED",
+            Self::Grapple => "MOD_GRAPPLE",
+            Self::Other(name) => name,
+        }
+    }
+
+    /// Whether this MOD is known by this library, i.e. it is not `Other`.
+    pub fn is_known(&self) -> bool {
+        !matches!(self, Self::Other(_))
+    }
+}
+
+impl From<&str> for MeansOfDeath {
+    /// Converts a MOD name as printed in the log into a MOD. Unknown names
+    /// become `Other`. The dictionary of names is created only in the first
+    /// call.
+    fn from(name: &str) -> Self {
+        static NAMES_CELL: OnceLock<HashMap<&'static str, MeansOfDeath>> =
+            OnceLock::new();
+        let names = NAMES_CELL.get_or_init(|| {
+            MeansOfDeath::ALL
+                .iter()
+                .map(|means| (means.name(), means.clone()))
+                .collect()
+        });
+        let name = name.trim();
+        names.get(name).cloned().unwrap_or_else(|| Self::Other(name.into()))
+    }
+}
+
+impl FromStr for MeansOfDeath {
+    type Err = Infallible;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Ok(Self::from(name))
+    }
+}
+
+impl fmt::Display for MeansOfDeath {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(self.name())
+    }
+}
+
+impl serde::Serialize for MeansOfDeath {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.name())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for MeansOfDeath {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let name = String::deserialize(deserializer)?;
+        Ok(Self::from(name.as_str()))
+    }
+}
+
+/// Table of numeric MOD codes, as printed in `Kill` events. Codes depend on
+/// how the game was built: `MISSIONPACK` builds have extra MODs before
+/// `MOD_GRAPPLE`, shifting its code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ModTable {
+    /// Quake III: Arena without `MISSIONPACK` (`MOD_GRAPPLE` is `23`).
+    #[default]
+    Baseq3,
+    /// Quake III: Team Arena, with `MISSIONPACK` (`MOD_GRAPPLE` is `28`).
+    MissionPack,
+}
+
+impl ModTable {
+    /// MODs of this table, indexed by their codes.
+    pub fn means(self) -> &'static [MeansOfDeath] {
+        match self {
+            Self::Baseq3 => BASEQ3_MEANS,
+            Self::MissionPack => MeansOfDeath::ALL,
+        }
+    }
+
+    /// Decodes a numeric MOD code into a MOD, if the code is valid.
+    pub fn decode(self, code: u32) -> Option<MeansOfDeath> {
+        let index = usize::try_from(code).ok()?;
+        self.means().get(index).cloned()
+    }
+
+    /// Encodes a MOD into its numeric code, if it is in this table.
+    pub fn encode(self, means: &MeansOfDeath) -> Option<u32> {
+        let index = self.means().iter().position(|other| other == means)?;
+        u32::try_from(index).ok()
+    }
+}
+
+/// MODs of Quake III: Arena without `MISSIONPACK`, indexed by their codes.
+const BASEQ3_MEANS: &[MeansOfDeath] = &[
+    MeansOfDeath::Unknown,
+    MeansOfDeath::Shotgun,
+    MeansOfDeath::Gauntlet,
+    MeansOfDeath::Machinegun,
+    MeansOfDeath::Grenade,
+    MeansOfDeath::GrenadeSplash,
+    MeansOfDeath::Rocket,
+    MeansOfDeath::RocketSplash,
+    MeansOfDeath::Plasma,
+    MeansOfDeath::PlasmaSplash,
+    MeansOfDeath::Railgun,
+    MeansOfDeath::Lightning,
+    MeansOfDeath::Bfg,
+    MeansOfDeath::BfgSplash,
+    MeansOfDeath::Water,
+    MeansOfDeath::Slime,
+    MeansOfDeath::Lava,
+    MeansOfDeath::Crush,
+    MeansOfDeath::Telefrag,
+    MeansOfDeath::Falling,
+    MeansOfDeath::Suicide,
+    MeansOfDeath::TargetLaser,
+    MeansOfDeath::TriggerHurt,
+    MeansOfDeath::Grapple,
+];
@@ -0,0 +1,42 @@
+use super::{MeansOfDeath, ModTable};
+
+#[test]
+fn from_known_name() {
+    assert_eq!(MeansOfDeath::from("MOD_ROCKET"), MeansOfDeath::Rocket);
+    assert_eq!(" MOD_GRAPPLE\n".parse(), Ok(MeansOfDeath::Grapple));
+}
+
+#[test]
+fn from_unknown_name() {
+    let means = MeansOfDeath::from("UT_MOD_KNIFE");
+    assert_eq!(means, MeansOfDeath::Other(String::from("UT_MOD_KNIFE")));
+    assert!(!means.is_known());
+    assert_eq!(means.to_string(), "UT_MOD_KNIFE");
+}
+
+#[test]
+fn display_roundtrip() {
+    for means in MeansOfDeath::ALL {
+        assert!(means.is_known());
+        assert_eq!(&MeansOfDeath::from(means.to_string().as_str()), means);
+    }
+}
+
+#[test]
+fn decode_baseq3() {
+    let table = ModTable::Baseq3;
+    assert_eq!(table.decode(6), Some(MeansOfDeath::Rocket));
+    assert_eq!(table.decode(22), Some(MeansOfDeath::TriggerHurt));
+    assert_eq!(table.decode(23), Some(MeansOfDeath::Grapple));
+    assert_eq!(table.decode(24), None);
+    assert_eq!(table.encode(&MeansOfDeath::Nail), None);
+}
+
+#[test]
+fn decode_missionpack() {
+    let table = ModTable::MissionPack;
+    assert_eq!(table.decode(23), Some(MeansOfDeath::Nail));
+    assert_eq!(table.decode(28), Some(MeansOfDeath::Grapple));
+    assert_eq!(table.encode(&MeansOfDeath::Grapple), Some(28));
+    assert_eq!(table.decode(29), None);
+}
@@ -0,0 +1,173 @@
+//! This module exposes a replay of a game's events, rebuilding the state of
+//! the game (connected players, their names, teams and scores) at any point
+//! of the match.
+
+use super::{
+    Game,
+    GameEvent,
+    GameEventKind,
+    KillCount,
+    Killer,
+    PlayerId,
+    PlayerName,
+    Team,
+    Timestamp,
+};
+use std::collections::BTreeMap;
+
+#[cfg(test)]
+mod test;
+
+/// State of a single player at some point of a game.
+#[derive(Debug, Clone, PartialEq, Eq, Default, serde::Serialize)]
+pub struct PlayerState {
+    /// The name the player was using, if already known.
+    pub name: Option<PlayerName>,
+    /// The team the player was in, if already known.
+    pub team: Option<Team>,
+    /// Whether the player was connected.
+    pub connected: bool,
+    /// Score in terms of killing, discounting `1` for each time the player
+    /// died because of the "world", as in the reports.
+    pub score: KillCount,
+}
+
+/// State of a game at some point of the match.
+#[derive(Debug, Clone, PartialEq, Eq, Default, serde::Serialize)]
+pub struct GameState {
+    /// Time of the last applied event, or the start time of the game if no
+    /// event was applied.
+    pub time: Timestamp,
+    /// How many events of the game were applied.
+    pub applied_events: usize,
+    /// Dictionary mapping IDs of players seen so far to their states.
+    pub players: BTreeMap<PlayerId, PlayerState>,
+}
+
+impl GameState {
+    /// The scoreboard of connected players: their names and scores, sorted by
+    /// score (highest first) and then by name. Players without a known name
+    /// are left out.
+    pub fn scoreboard(&self) -> Vec<(&PlayerName, KillCount)> {
+        let mut scoreboard: Vec<_> = self
+            .players
+            .values()
+            .filter(|player| player.connected)
+            .filter_map(|player| Some((player.name.as_ref()?, player.score)))
+            .collect();
+        scoreboard.sort_by(|(left_name, left), (right_name, right)| {
+            right.cmp(left).then_with(|| left_name.cmp(right_name))
+        });
+        scoreboard
+    }
+}
+
+/// A replay of a game, applying its events one by one, in order.
+#[derive(Debug, Clone)]
+pub struct Replay<'game> {
+    /// The game being replayed.
+    game: &'game Game,
+    /// The state after the events applied so far.
+    state: GameState,
+}
+
+impl<'game> Replay<'game> {
+    /// Starts replaying the given game, with no event applied.
+    pub fn new(game: &'game Game) -> Self {
+        let state = GameState { time: game.start_time, ..GameState::default() };
+        Self { game, state }
+    }
+
+    /// The state after the events applied so far.
+    pub fn state(&self) -> &GameState {
+        &self.state
+    }
+
+    /// Consumes the replay and returns the state after the events applied so
+    /// far.
+    pub fn into_state(self) -> GameState {
+        self.state
+    }
+
+    /// The next event to be applied, if any.
+    pub fn peek(&self) -> Option<&'game GameEvent> {
+        self.game.events.get(self.state.applied_events)
+    }
+
+    /// Applies the next event, if any, and returns it.
+    pub fn step(&mut self) -> Option<&'game GameEvent> {
+        let event = self.peek()?;
+        self.state.applied_events += 1;
+        self.state.time = event.time;
+        match &event.kind {
+            GameEventKind::Connect { player } => {
+                self.player_mut(*player).connected = true;
+            },
+            GameEventKind::Disconnect { player } => {
+                self.player_mut(*player).connected = false;
+            },
+            GameEventKind::PlayerInfoChanged { player, name, team } => {
+                let player = self.player_mut(*player);
+                player.name = Some(name.clone());
+                if let Some(team) = team {
+                    player.team = Some(*team);
+                }
+            },
+            GameEventKind::Kill { index } => {
+                let Some(kill) = self.game.kills.get(*index) else {
+                    log::error!("Bad replay: kill {} was not found", index);
+                    return Some(event);
+                };
+                match kill.killer {
+                    Killer::Player(killer) => {
+                        self.player_mut(killer).score += 1;
+                    },
+                    Killer::World => {
+                        self.player_mut(kill.target).score -= 1;
+                    },
+                }
+            },
+        }
+        Some(event)
+    }
+
+    /// Applies every event that happened up to the given time, inclusive.
+    pub fn advance_to_time(&mut self, time: Timestamp) {
+        while self.peek().is_some_and(|event| event.time <= time) {
+            self.step();
+        }
+    }
+
+    /// Applies events until the given number of events is applied, or the
+    /// events run out.
+    pub fn advance_to_event(&mut self, applied_events: usize) {
+        while self.state.applied_events < applied_events {
+            if self.step().is_none() {
+                break;
+            }
+        }
+    }
+
+    /// State of the player with the given ID, created if missing.
+    fn player_mut(&mut self, id: PlayerId) -> &mut PlayerState {
+        self.state.players.entry(id).or_default()
+    }
+}
+
+impl Game {
+    /// Rebuilds the state of this game at the given time, applying every
+    /// event that happened up to it, inclusive. Like event timestamps, the
+    /// time is counted from when the server started the current map.
+    pub fn state_at_time(&self, time: Timestamp) -> GameState {
+        let mut replay = Replay::new(self);
+        replay.advance_to_time(time);
+        replay.into_state()
+    }
+
+    /// Rebuilds the state of this game after the given number of events.
+    pub fn state_at_event(&self, applied_events: usize) -> GameState {
+        let mut replay = Replay::new(self);
+        replay.advance_to_event(applied_events);
+        replay.into_state()
+    }
+}
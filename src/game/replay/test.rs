@@ -0,0 +1,120 @@
+use super::{GameState, PlayerState, Replay};
+use crate::game::{
+    Game,
+    GameEvent,
+    GameEventKind,
+    Kill,
+    Killer,
+    MeansOfDeath,
+    PlayerId,
+    PlayerName,
+    Team,
+    Timestamp,
+};
+use std::collections::{BTreeMap, HashMap};
+
+fn event(kind: GameEventKind, time: Timestamp) -> GameEvent {
+    GameEvent { kind, time }
+}
+
+fn info_changed(player: PlayerId, name: &str, team: Team) -> GameEventKind {
+    let name = PlayerName::from(name);
+    GameEventKind::PlayerInfoChanged { player, name, team: Some(team) }
+}
+
+fn kill(killer: Killer, target: PlayerId, time: Timestamp) -> Kill {
+    Kill { killer, target, means: MeansOfDeath::from("MOD_SHOTGUN"), time }
+}
+
+fn game() -> Game {
+    Game {
+        start_time: 10,
+        players: HashMap::from([
+            (2, PlayerName::from("Zeh")),
+            (3, PlayerName::from("Mal")),
+        ]),
+        kills: vec![
+            kill(Killer::Player(2), 3, 30),
+            kill(Killer::World, 2, 40),
+            kill(Killer::Player(3), 2, 50),
+        ],
+        events: vec![
+            event(GameEventKind::Connect { player: 2 }, 10),
+            event(info_changed(2, "Zeh", Team::Red), 10),
+            event(GameEventKind::Connect { player: 3 }, 20),
+            event(info_changed(3, "Isgalamido", Team::Blue), 20),
+            event(GameEventKind::Kill { index: 0 }, 30),
+            event(info_changed(3, "Mal", Team::Blue), 35),
+            event(GameEventKind::Kill { index: 1 }, 40),
+            event(GameEventKind::Kill { index: 2 }, 50),
+            event(GameEventKind::Disconnect { player: 2 }, 60),
+        ],
+        ..Game::default()
+    }
+}
+
+#[test]
+fn state_before_any_event() {
+    let expected = GameState { time: 10, ..GameState::default() };
+    assert_eq!(expected, game().state_at_event(0));
+    assert_eq!(expected, game().state_at_time(5));
+}
+
+#[test]
+fn state_at_time() {
+    let expected = GameState {
+        time: 35,
+        applied_events: 6,
+        players: BTreeMap::from([
+            (2, PlayerState {
+                name: Some(PlayerName::from("Zeh")),
+                team: Some(Team::Red),
+                connected: true,
+                score: 1,
+            }),
+            (3, PlayerState {
+                name: Some(PlayerName::from("Mal")),
+                team: Some(Team::Blue),
+                connected: true,
+                score: 0,
+            }),
+        ]),
+    };
+    assert_eq!(expected, game().state_at_time(39));
+}
+
+#[test]
+fn state_at_event_index() {
+    let state = game().state_at_event(4);
+    assert_eq!(state.time, 20);
+    assert_eq!(state.players[&3].name.as_deref(), Some("Isgalamido"));
+    assert_eq!(state.players[&3].score, 0);
+
+    let state = game().state_at_event(100);
+    assert_eq!(state.applied_events, 9);
+}
+
+#[test]
+fn scoreboard_of_connected_players() {
+    let game = game();
+    let state = game.state_at_time(50);
+    let (mal, zeh) = (PlayerName::from("Mal"), PlayerName::from("Zeh"));
+    assert_eq!(vec![(&mal, 1), (&zeh, 0)], state.scoreboard());
+
+    let state = game.state_at_time(60);
+    assert_eq!(vec![(&mal, 1)], state.scoreboard());
+}
+
+#[test]
+fn replay_step_by_step() {
+    let game = game();
+    let mut replay = Replay::new(&game);
+    let mut count = 0;
+    while let Some(event) = replay.step() {
+        count += 1;
+        assert_eq!(replay.state().time, event.time);
+    }
+    assert_eq!(count, game.events.len());
+    assert!(replay.peek().is_none());
+    assert!(!replay.state().players[&2].connected);
+}
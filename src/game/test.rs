@@ -0,0 +1,45 @@
+use super::{color_pieces, format_timestamp, strip_color_codes, ColorPiece};
+
+#[test]
+fn format_timestamps() {
+    assert_eq!(format_timestamp(0), "0:00");
+    assert_eq!(format_timestamp(9 * 60 + 5), "9:05");
+    assert_eq!(format_timestamp(75 * 60), "75:00");
+}
+
+#[test]
+fn strip_color_codes_removes_escapes() {
+    assert_eq!(strip_color_codes("^1Isga^7lamido"), "Isgalamido");
+    assert_eq!(strip_color_codes(" ^^7Zeh^"), "^Zeh^");
+    assert_eq!(strip_color_codes("Dono da Bola"), "Dono da Bola");
+}
+
+#[test]
+fn split_color_pieces() {
+    let pieces: Vec<_> = color_pieces("^1Z^9^^").collect();
+    let expected = [
+        ColorPiece::Color(1),
+        ColorPiece::Char('Z'),
+        ColorPiece::Color(1),
+        ColorPiece::Char('^'),
+        ColorPiece::Char('^'),
+    ];
+    assert_eq!(pieces, expected);
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn names_match_serialization() {
+    use super::{GameType, Team};
+
+    for code in 0 .. 8 {
+        let game_type = GameType::from_code(code);
+        let serialized = serde_json::to_value(game_type).unwrap();
+        assert_eq!(serialized, game_type.name());
+    }
+    assert_eq!(GameType::Other(9).to_string(), "other");
+    for code in 0 .. 4 {
+        let team = Team::from_code(code).unwrap();
+        assert_eq!(serde_json::to_value(team).unwrap(), team.name());
+    }
+}
@@ -0,0 +1,212 @@
+//! This module exposes the weapons behind Means of Death (MOD) and their
+//! categories, so that e.g. `MOD_ROCKET` and `MOD_ROCKET_SPLASH` are both
+//! rocket launcher kills.
+
+use super::MeansOfDeath;
+use std::fmt;
+
+#[cfg(test)]
+mod test;
+
+/// A weapon (or item) that kills, as a group of MODs.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Weapon {
+    /// Gauntlet.
+    Gauntlet,
+    /// Machine gun.
+    MachineGun,
+    /// Shotgun.
+    Shotgun,
+    /// Grenade launcher.
+    GrenadeLauncher,
+    /// Rocket launcher.
+    RocketLauncher,
+    /// Lightning gun.
+    LightningGun,
+    /// Railgun.
+    Railgun,
+    /// Plasma gun.
+    PlasmaGun,
+    /// BFG10K.
+    Bfg10k,
+    /// Grappling hook.
+    GrapplingHook,
+    /// Nailgun, from `MISSIONPACK`.
+    Nailgun,
+    /// Chaingun, from `MISSIONPACK`.
+    Chaingun,
+    /// Proximity mine launcher, from `MISSIONPACK`.
+    ProximityLauncher,
+    /// Kamikaze holdable item, from `MISSIONPACK`.
+    Kamikaze,
+    /// Not a weapon, such as falling or lava, or an unknown MOD.
+    Other,
+}
+
+impl Weapon {
+    /// All weapons, `Other` being the last one.
+    pub const ALL: &'static [Self] = &[
+        Self::Gauntlet,
+        Self::MachineGun,
+        Self::Shotgun,
+        Self::GrenadeLauncher,
+        Self::RocketLauncher,
+        Self::LightningGun,
+        Self::Railgun,
+        Self::PlasmaGun,
+        Self::Bfg10k,
+        Self::GrapplingHook,
+        Self::Nailgun,
+        Self::Chaingun,
+        Self::ProximityLauncher,
+        Self::Kamikaze,
+        Self::Other,
+    ];
+
+    /// The name of this weapon as serialized, e.g. `rocket_launcher`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Gauntlet => "gauntlet",
+            Self::MachineGun => "machine_gun",
+            Self::Shotgun => "shotgun",
+            Self::GrenadeLauncher => "grenade_launcher",
+            Self::RocketLauncher => "rocket_launcher",
+            Self::LightningGun => "lightning_gun",
+            Self::Railgun => "railgun",
+            Self::PlasmaGun => "plasma_gun",
+            Self::Bfg10k => "bfg10k",
+            Self::GrapplingHook => "grappling_hook",
+            Self::Nailgun => "nailgun",
+            Self::Chaingun => "chaingun",
+            Self::ProximityLauncher => "proximity_launcher",
+            Self::Kamikaze => "kamikaze",
+            Self::Other => "other",
+        }
+    }
+}
+
+impl fmt::Display for Weapon {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(self.name())
+    }
+}
+
+/// A category of MODs, by how the kill happened.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum WeaponCategory {
+    /// Instant hit, such as railgun or machine gun.
+    Hitscan,
+    /// Direct hit of a projectile, such as a rocket.
+    Projectile,
+    /// Explosion damage, such as a rocket's splash.
+    Splash,
+    /// Caused by the map, such as falling or lava.
+    Environmental,
+    /// Spawning or teleporting on top of someone.
+    Telefrag,
+    /// Suicide command or an unknown MOD.
+    Other,
+}
+
+impl WeaponCategory {
+    /// All categories, `Other` being the last one.
+    pub const ALL: &'static [Self] = &[
+        Self::Hitscan,
+        Self::Projectile,
+        Self::Splash,
+        Self::Environmental,
+        Self::Telefrag,
+        Self::Other,
+    ];
+}
+
+impl MeansOfDeath {
+    /// The weapon that caused this MOD, `Weapon::Other` if none.
+    pub fn weapon(&self) -> Weapon {
+        match self {
+            Self::Gauntlet => Weapon::Gauntlet,
+            Self::Machinegun => Weapon::MachineGun,
+            Self::Shotgun => Weapon::Shotgun,
+            Self::Grenade | Self::GrenadeSplash => Weapon::GrenadeLauncher,
+            Self::Rocket | Self::RocketSplash => Weapon::RocketLauncher,
+            Self::Lightning => Weapon::LightningGun,
+            Self::Railgun => Weapon::Railgun,
+            Self::Plasma | Self::PlasmaSplash => Weapon::PlasmaGun,
+            Self::Bfg | Self::BfgSplash => Weapon::Bfg10k,
+            Self::Grapple => Weapon::GrapplingHook,
+            Self::Nail => Weapon::Nailgun,
+            Self::Chaingun => Weapon::Chaingun,
+            Self::ProximityMine | Self::Juiced => Weapon::ProximityLauncher,
+            Self::Kamikaze => Weapon::Kamikaze,
+            Self::Unknown
+            | Self::Water
+            | Self::Slime
+            | Self::Lava
+            | Self::Crush
+            | Self::Telefrag
+            | Self::Falling
+            | Self::Suicide
+            | Self::TargetLaser
+            | Self::TriggerHurt
+            | Self::Other(_) => Weapon::Other,
+        }
+    }
+
+    /// The category of this MOD.
+    pub fn category(&self) -> WeaponCategory {
+        match self {
+            Self::Gauntlet
+            | Self::Machinegun
+            | Self::Shotgun
+            | Self::Lightning
+            | Self::Railgun
+            | Self::Chaingun => WeaponCategory::Hitscan,
+            Self::Grenade
+            | Self::Rocket
+            | Self::Plasma
+            | Self::Bfg
+            | Self::Nail
+            | Self::Grapple => WeaponCategory::Projectile,
+            Self::GrenadeSplash
+            | Self::RocketSplash
+            | Self::PlasmaSplash
+            | Self::BfgSplash
+            | Self::ProximityMine
+            | Self::Juiced
+            | Self::Kamikaze => WeaponCategory::Splash,
+            Self::Water
+            | Self::Slime
+            | Self::Lava
+            | Self::Crush
+            | Self::Falling
+            | Self::TargetLaser
+            | Self::TriggerHurt => WeaponCategory::Environmental,
+            Self::Telefrag => WeaponCategory::Telefrag,
+            Self::Unknown | Self::Suicide | Self::Other(_) => {
+                WeaponCategory::Other
+            },
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use super::{Weapon, WeaponCategory};
+use crate::game::MeansOfDeath;
+
+#[test]
+fn splash_and_direct_hits_share_weapon() {
+    assert_eq!(MeansOfDeath::Rocket.weapon(), Weapon::RocketLauncher);
+    assert_eq!(MeansOfDeath::RocketSplash.weapon(), Weapon::RocketLauncher);
+    assert_eq!(MeansOfDeath::Rocket.category(), WeaponCategory::Projectile);
+    assert_eq!(MeansOfDeath::RocketSplash.category(), WeaponCategory::Splash);
+}
+
+#[test]
+fn non_weapons() {
+    assert_eq!(MeansOfDeath::Falling.weapon(), Weapon::Other);
+    assert_eq!(MeansOfDeath::Falling.category(), WeaponCategory::Environmental);
+    assert_eq!(MeansOfDeath::Telefrag.weapon(), Weapon::Other);
+    assert_eq!(MeansOfDeath::Telefrag.category(), WeaponCategory::Telefrag);
+    let other = MeansOfDeath::from("MOD_SWORD");
+    assert_eq!(other.weapon(), Weapon::Other);
+    assert_eq!(other.category(), WeaponCategory::Other);
+}
+
+#[test]
+fn every_weapon_is_reachable() {
+    for weapon in Weapon::ALL {
+        let found =
+            MeansOfDeath::ALL.iter().any(|means| means.weapon() == *weapon);
+        assert!(found, "no MOD for {:?}", weapon);
+    }
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn names_match_serialization() {
+    for weapon in Weapon::ALL {
+        let serialized = serde_json::to_value(weapon).unwrap();
+        assert_eq!(serialized, weapon.name());
+    }
+}
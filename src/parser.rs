@@ -3,21 +3,43 @@
 use crate::{
     error::Result,
     game::{
-        all_means_of_death,
+        Award,
+        Dialect,
+        FlagAction,
+        FlagEvent,
         Game,
+        GameCount,
+        GameEvent,
+        GameEventKind,
+        GainedAward,
         Kill,
         Killer,
         MeansOfDeath,
         PlayerId,
         PlayerName,
+        Team,
+        Timestamp,
     },
 };
+use checkpoint::{ByteOffset, Checkpoint};
+use follow::{FileIdentity, Follower};
+use input::{Decoder, LogFiles};
 use std::{
     collections::hash_map,
-    io::{self, BufRead, BufReader, Read},
+    fs::File,
+    io::{self, BufRead, BufReader, Read, Seek, SeekFrom},
     mem,
+    path::PathBuf,
 };
 
+pub mod checkpoint;
+pub mod follow;
+pub mod input;
+#[cfg(feature = "parallel")]
+pub mod parallel;
+#[cfg(feature = "async")]
+pub mod stream;
+
 #[cfg(test)]
 mod test;
 
@@ -32,6 +54,15 @@ pub struct Parser<R> {
     line_buf: String,
     /// Parser state, fed with lines from the reader.
     state: State,
+    /// Dialect of the games in the log, detected for each game if not set.
+    dialect: Option<Dialect>,
+    /// Offset of the first byte not read yet.
+    offset: ByteOffset,
+    /// How many games were yielded so far, since the start of the log.
+    games: GameCount,
+    /// Whether the unfinished game at the end of the log is kept for a
+    /// checkpoint instead of being yielded.
+    incremental: bool,
 }
 
 impl<R> Parser<BufReader<R>>
@@ -45,6 +76,113 @@ where
     }
 }
 
+impl<R> Parser<BufReader<Decoder<R>>>
+where
+    R: Read,
+{
+    /// Creates the parser from a reader object that might be compressed,
+    /// decompressing it on the fly. The compression is detected by magic
+    /// bytes, and decoding each format requires its cargo feature (`gzip`,
+    /// `zstd` or `bzip2`).
+    pub fn decompressed(reader: R) -> Result<Self> {
+        Ok(Self::new(Decoder::new(reader)?))
+    }
+}
+
+impl<R> Parser<BufReader<R>>
+where
+    R: Read + Seek,
+{
+    /// Resumes parsing the same log a checkpoint was taken from, seeking the
+    /// reader to the checkpoint's offset and restoring its unfinished game.
+    /// Games yielded before the checkpoint are not yielded again, and the
+    /// parser is incremental (see [`Parser::incremental`]). If the log is now
+    /// shorter than when the checkpoint was taken, it was truncated, so it is
+    /// parsed again from its start as in [`Checkpoint::restart`]: the
+    /// unfinished game of the checkpoint is lost, while games keep being
+    /// numbered after the previous ones.
+    pub fn resume(mut reader: R, checkpoint: Checkpoint) -> Result<Self> {
+        let length = reader.seek(SeekFrom::End(0))?;
+        let checkpoint = if length < checkpoint.length.max(checkpoint.offset) {
+            log::warn!(
+                "The log was truncated since its checkpoint, parsing it again \
+                 from its start"
+            );
+            checkpoint.restart()
+        } else {
+            checkpoint
+        };
+        reader.seek(SeekFrom::Start(checkpoint.offset))?;
+        let mut this = Self::new(reader).incremental();
+        this.offset = checkpoint.offset;
+        this.games = checkpoint.games;
+        if let Some(game) = checkpoint.game {
+            this.state = State::InGame(Box::new(game));
+        }
+        Ok(this)
+    }
+}
+
+impl Parser<BufReader<File>> {
+    /// Resumes parsing a log file as in [`Parser::resume`]. If the file is not
+    /// the one the checkpoint was taken from, e.g. the log was rotated, it is
+    /// parsed from its start as in [`Checkpoint::restart`]. Two things are
+    /// then lost, with only a warning: games appended to the old file after
+    /// the checkpoint, and the unfinished game of the checkpoint. To keep
+    /// them, parse the rest of the old file before it is rotated.
+    pub fn resume_file(file: File, checkpoint: Checkpoint) -> Result<Self> {
+        let file_identity = FileIdentity::of(&file.metadata()?);
+        if checkpoint.file.is_some() && checkpoint.file != file_identity {
+            log::warn!(
+                "The log is not the file of its checkpoint, parsing it from \
+                 its start"
+            );
+            return Self::resume(file, checkpoint.restart());
+        }
+        Self::resume(file, checkpoint)
+    }
+
+    /// Takes a checkpoint of the parser as in [`Parser::checkpoint`], along
+    /// with the identity and length of the log file, so that rotation and
+    /// truncation are detected by [`Parser::resume_file`].
+    pub fn file_checkpoint(&self) -> Result<Checkpoint> {
+        let metadata = self.reader.get_ref().metadata()?;
+        Ok(Checkpoint {
+            file: FileIdentity::of(&metadata),
+            length: metadata.len().max(self.offset),
+            ..self.checkpoint()
+        })
+    }
+}
+
+impl Parser<BufReader<LogFiles>> {
+    /// Creates the parser from an ordered list of log files, such as a log and
+    /// its rotated copies from the oldest to the newest, read as a single
+    /// stream. Games split across files are joined, and games are yielded in
+    /// a sequence that does not restart at each file. Compressed files are
+    /// decompressed as in [`Parser::decompressed`].
+    pub fn from_paths<I, P>(paths: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<PathBuf>,
+    {
+        Self::new(LogFiles::new(paths))
+    }
+}
+
+impl Parser<BufReader<Follower>> {
+    /// Creates the parser following a live log file, like `tail -F`. The
+    /// iterator never ends: it yields each game as soon as it finishes, and
+    /// waits for the server to write more games, following the log through
+    /// truncation and rotation.
+    pub fn follow<P>(path: P) -> Result<Self>
+    where
+        P: Into<PathBuf>,
+    {
+        Ok(Self::new(Follower::new(path)?))
+    }
+}
+
 impl<R> Parser<R>
 where
     R: BufRead,
@@ -52,13 +190,56 @@ where
     /// Creates the parser from a buffered reader object (typically wrapping a
     /// file or equivalent).
     pub fn with_bufread(reader: R) -> Self {
-        Self { reader, line_buf: String::new(), state: State::NoGame }
+        Self {
+            reader,
+            line_buf: String::new(),
+            state: State::NoGame,
+            dialect: None,
+            offset: 0,
+            games: 0,
+            incremental: false,
+        }
+    }
+
+    /// Makes the parser incremental: at the end of the log, the unfinished
+    /// game is kept for a checkpoint instead of being yielded, and so is a
+    /// last line without a line feed, since the server might still be writing
+    /// it.
+    pub fn incremental(mut self) -> Self {
+        self.incremental = true;
+        self
+    }
+
+    /// Takes a checkpoint of the parser, from which parsing can be resumed
+    /// with [`Parser::resume`].
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            offset: self.offset,
+            game: match &self.state {
+                State::InGame(game) => Some(Game::clone(game)),
+                State::NoGame => None,
+            },
+            games: self.games,
+            file: None,
+            length: self.offset,
+        }
+    }
+
+    /// Sets the dialect of all games in the log. By default, the dialect is
+    /// detected for each game from its `InitGame` event.
+    pub fn with_dialect(mut self, dialect: Dialect) -> Self {
+        self.dialect = Some(dialect);
+        self
     }
 
     /// Finishes the parser when the file reaches its end. If a game was still
-    /// active, it is returned.
+    /// active, it is returned, unless the parser is incremental.
     fn finish(&mut self) -> Option<Game> {
-        self.state.finish_game()
+        if self.incremental {
+            None
+        } else {
+            self.state.finish_game()
+        }
     }
 
     /// Reads a line from the underlying reader and returns whether a line was
@@ -72,16 +253,28 @@ where
                 Ok(false)
             },
             Err(error) => Err(error.into()),
-            _ => Ok(true),
+            Ok(_) if self.incremental && !self.line_buf.ends_with('\n') => {
+                Ok(false)
+            },
+            Ok(count) => {
+                self.offset += count as ByteOffset;
+                Ok(true)
+            },
         }
     }
 
+    /// Counts a game being yielded.
+    fn yield_game(&mut self, game: Game) -> Option<Result<Game>> {
+        self.games += 1;
+        Some(Ok(game))
+    }
+
     /// Processes a line previously read from the underlying reader by feeding
     /// the line to the state.
     ///
     /// If a game is finished during this method call, the game is returned.
     fn process_line(&mut self) -> Option<Game> {
-        self.state.process_line(&self.line_buf)
+        self.state.process_line(&self.line_buf, self.dialect)
     }
 }
 
@@ -94,13 +287,16 @@ where
     fn next(&mut self) -> Option<Self::Item> {
         loop {
             match self.read_line() {
-                Ok(false) => return self.finish().map(Ok),
+                Ok(false) => {
+                    let game = self.finish()?;
+                    return self.yield_game(game);
+                },
                 Ok(true) => (),
                 Err(error) => return Some(Err(error)),
             }
 
             if let Some(game) = self.process_line() {
-                return Some(Ok(game));
+                return self.yield_game(game);
             }
         }
     }
@@ -109,6 +305,8 @@ where
 /// Raw structure for an event that occurs in the log file.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 struct RawEvent<'line> {
+    /// Time of the event, if the line's time prefix is valid.
+    time: Option<Timestamp>,
     /// Key of the event, such as "InitGame".
     key: &'line str,
     /// Raw representation of the event's payload.
@@ -121,66 +319,247 @@ impl<'line> RawEvent<'line> {
     /// returned.
     fn from_line(line: &'line str) -> Option<Self> {
         let split_index = line.find(char::is_alphabetic)?;
+        let time = parse_timestamp(&line[.. split_index]);
         let event_str = &line[split_index ..];
         let (key, raw_data) = event_str.split_once(':')?;
-        Some(Self { key: key.trim(), raw_data: raw_data.trim() })
+        Some(Self { time, key: key.trim(), raw_data: raw_data.trim() })
     }
 
-    /// Parses the raw event representation into a structured event. If the
-    /// event key represents an unused event, it is ignored. If the event
-    /// data does not seem to be valid, it is ignored as well. Ignored =
-    /// returns `None`.
-    fn parse(self) -> Option<Event> {
+    /// Parses the raw event representation into a structured event, as
+    /// written by the given dialect. If the event key represents an unused
+    /// event, it is ignored. If the event data does not seem to be valid, it
+    /// is ignored as well. Ignored = returns `None`.
+    fn parse(self, dialect: Dialect) -> Option<Event> {
         match self.key {
-            "InitGame" => Some(Event::Init),
-            "ShutdownGame" => Some(Event::Shutdown),
+            "InitGame" => Some(self.parse_init_game()),
+            "ShutdownGame" => Some(Event::Shutdown { time: self.time }),
+            "ClientConnect" => self.parse_client_connection(true),
+            "ClientDisconnect" => self.parse_client_connection(false),
             "ClientUserinfoChanged" => self.parse_client_user_info_changed(),
-            "Kill" => self.parse_kill(),
+            "Kill" => self.parse_kill(dialect),
+            "Award" => self.parse_award(),
+            "Item" => self.parse_item(),
+            "Flag" if dialect == Dialect::UrbanTerror => {
+                self.parse_urban_terror_flag()
+            },
+            "CTF" if dialect == Dialect::OpenArena => {
+                self.parse_open_arena_ctf()
+            },
             _ => None,
         }
     }
 
+    /// Parses event data specifically when the key is `InitGame`. Server
+    /// variables are best-effort: an invalid or missing game type code is
+    /// left out.
+    fn parse_init_game(self) -> Event {
+        let game_type_code = info_value(self.raw_data, "g_gametype")
+            .and_then(|code| code.trim().parse().ok());
+        let non_empty_value = |key| {
+            let value = info_value(self.raw_data, key)?.trim();
+            (!value.is_empty()).then(|| String::from(value))
+        };
+        let game_name = non_empty_value("gamename");
+        let version = non_empty_value("version");
+        let map = non_empty_value("mapname");
+        Event::Init {
+            game_type_code,
+            game_name,
+            version,
+            map,
+            time: self.time,
+        }
+    }
+
     /// Parses event data specifically when the key is `ClientUserinfoChanged`.
     fn parse_client_user_info_changed(self) -> Option<Event> {
         let (id_str, tail) = self.raw_data.trim().split_once(' ')?;
         let id = id_str.trim().parse().ok()?;
-        let (_, name_trailing) = tail.split_once("n\\")?;
-        let name = match name_trailing.split_once("\\") {
-            Some((name, _)) => name,
-            None => name_trailing,
-        };
-        Some(Event::PlayerNameChanged { id, name: PlayerName::from(name) })
+        let name = info_value(tail, "n")?;
+        let team = info_value(tail, "t")
+            .and_then(|code| code.trim().parse().ok())
+            .and_then(Team::from_code);
+        let name = PlayerName::from(name);
+        Some(Event::PlayerInfoChanged { id, name, team, time: self.time })
+    }
+
+    /// Parses event data specifically when the key is `ClientConnect` (if
+    /// `connected` is `true`) or `ClientDisconnect` (otherwise).
+    fn parse_client_connection(self, connected: bool) -> Option<Event> {
+        let id = self.raw_data.trim().parse().ok()?;
+        Some(if connected {
+            Event::Connect { id, time: self.time }
+        } else {
+            Event::Disconnect { id, time: self.time }
+        })
     }
 
-    /// Parses event data specifically when the key is `Kill`.
-    fn parse_kill(self) -> Option<Event> {
+    /// Parses event data specifically when the key is `Kill`. The MOD is
+    /// taken from its name, or decoded from its numeric code with the
+    /// dialect's table if the name is missing.
+    fn parse_kill(self, dialect: Dialect) -> Option<Event> {
         let (killer_id_str, tail) = self.raw_data.trim().split_once(' ')?;
         let (target_id_str, tail) = tail.trim().split_once(' ')?;
-        let (_, tail) = tail.trim().split_once(':')?;
+        let (means_code_str, tail) = tail.trim().split_once(':')?;
         let killer = if tail.trim().starts_with("<world> killed") {
             Killer::World
         } else {
             Killer::Player(killer_id_str.trim().parse().ok()?)
         };
         let target = target_id_str.trim().parse().ok()?;
-        let (_, mean_str) = tail.rsplit_once("by ")?;
-        let (_, means) = all_means_of_death().get_full(mean_str.trim())?;
-        Some(Event::Kill { killer, target, means })
+        let means = match tail.rsplit_once("by ") {
+            Some((_, means_str)) if !means_str.trim().is_empty() => {
+                MeansOfDeath::from(means_str)
+            },
+            _ => {
+                let code = means_code_str.trim().parse().ok()?;
+                dialect.mod_table()?.decode(code)?
+            },
+        };
+        Some(Event::Kill { killer, target, means, time: self.time })
+    }
+
+    /// Parses event data specifically when the key is `Award`, e.g.
+    /// `3 2: Zeh gained the IMPRESSIVE award!`. The award's integer value is
+    /// not used, since the award name is enough.
+    fn parse_award(self) -> Option<Event> {
+        let (id_str, tail) = self.raw_data.trim().split_once(' ')?;
+        let id = id_str.trim().parse().ok()?;
+        let (_, award_str) = tail.rsplit_once("gained the ")?;
+        let award_name = award_str.trim().strip_suffix("award!")?;
+        let award = Award::from_log_name(award_name)?;
+        Some(Event::Award { id, award, time: self.time })
     }
+
+    /// Parses event data specifically when the key is `Item`, e.g.
+    /// `2 team_CTF_blueflag`. Only team flags are relevant, other items are
+    /// ignored.
+    fn parse_item(self) -> Option<Event> {
+        let (id_str, item) = self.raw_data.trim().split_once(' ')?;
+        let id = id_str.trim().parse().ok()?;
+        let flag = flag_of_item(item)?;
+        Some(Event::FlagTouch { id, flag, time: self.time })
+    }
+
+    /// Parses event data specifically when the key is `Flag`, in Urban
+    /// Terror, e.g. `0 2: team_CTF_blueflag`. The second number is the
+    /// action: `0` drops, `1` returns and `2` captures the flag.
+    fn parse_urban_terror_flag(self) -> Option<Event> {
+        let (numbers, item) = self.raw_data.trim().split_once(':')?;
+        let (id_str, action_str) = numbers.trim().split_once(' ')?;
+        let id = id_str.trim().parse().ok()?;
+        let flag = flag_of_item(item)?;
+        let action = match action_str.trim() {
+            "0" => FlagAction::Drop { killer: None },
+            "1" => FlagAction::Return,
+            "2" => FlagAction::Capture,
+            _ => return None,
+        };
+        Some(Event::Flag { id, flag, action, time: self.time })
+    }
+
+    /// Parses event data specifically when the key is `CTF`, in OpenArena,
+    /// e.g. `1 2 0: Zeh got the BLUE flag!`. The numbers are the player, the
+    /// team of the flag and the action: `0` picks up, `1` captures and `2`
+    /// returns the flag, while `3` (a carrier killed) is already known from
+    /// the `Kill` event.
+    fn parse_open_arena_ctf(self) -> Option<Event> {
+        let (numbers, _) = self.raw_data.trim().split_once(':')?;
+        let mut numbers = numbers.split_whitespace();
+        let id = numbers.next()?.parse().ok()?;
+        let flag = Team::from_code(numbers.next()?.parse().ok()?)?;
+        let action = match numbers.next()? {
+            "0" => FlagAction::Pickup,
+            "1" => FlagAction::Capture,
+            "2" => FlagAction::Return,
+            _ => return None,
+        };
+        Some(Event::Flag { id, flag, action, time: self.time })
+    }
+}
+
+/// Parses the time prefix of a line, such as `" 12:34 "`, into seconds. Only
+/// the last word of the prefix is considered, so garbage before the time is
+/// ignored.
+fn parse_timestamp(prefix: &str) -> Option<Timestamp> {
+    let (minutes, seconds) = prefix.split_whitespace().last()?.split_once(':')?;
+    let minutes: Timestamp = minutes.parse().ok()?;
+    let seconds: Timestamp = seconds.parse().ok()?;
+    Some(minutes * 60 + seconds)
+}
+
+/// Converts the name of a flag item, such as `team_CTF_redflag`, into the
+/// team owning the flag.
+fn flag_of_item(item: &str) -> Option<Team> {
+    match item.trim() {
+        "team_CTF_redflag" => Some(Team::Red),
+        "team_CTF_blueflag" => Some(Team::Blue),
+        _ => None,
+    }
+}
+
+/// Finds the value of a key in an info string, such as
+/// `n\Isgalamido\t\0`, where keys and values are separated by backslashes.
+fn info_value<'info>(info: &'info str, key: &str) -> Option<&'info str> {
+    let info = info.trim();
+    let mut parts = info.strip_prefix('\\').unwrap_or(info).split('\\');
+    while let Some(part_key) = parts.next() {
+        let value = parts.next()?;
+        if part_key == key {
+            return Some(value);
+        }
+    }
+    None
 }
 
 /// A structured event representation.
 #[derive(Debug, Clone, PartialEq, Eq)]
 enum Event {
-    /// Game initialization.
-    Init,
-    /// Game shutdown.
-    Shutdown,
-    /// A name of the player with the given ID has changed to the given name.
-    PlayerNameChanged { id: PlayerId, name: PlayerName },
+    /// Game initialization, with the game's settings, possibly at a known
+    /// time. The game type code depends on the dialect, which is told by the
+    /// game name and version.
+    Init {
+        game_type_code: Option<u32>,
+        game_name: Option<String>,
+        version: Option<String>,
+        map: Option<String>,
+        time: Option<Timestamp>,
+    },
+    /// Game shutdown, possibly at a known time.
+    Shutdown { time: Option<Timestamp> },
+    /// The player with the given ID connected, possibly at a known time.
+    Connect { id: PlayerId, time: Option<Timestamp> },
+    /// The player with the given ID disconnected, possibly at a known time.
+    Disconnect { id: PlayerId, time: Option<Timestamp> },
+    /// The information of the player with the given ID has changed, setting
+    /// their name and possibly their team, possibly at a known time.
+    PlayerInfoChanged {
+        id: PlayerId,
+        name: PlayerName,
+        team: Option<Team>,
+        time: Option<Timestamp>,
+    },
     /// Someone (target) was killed (by the killer) with the given means of
-    /// death.
-    Kill { killer: Killer, target: PlayerId, means: MeansOfDeath },
+    /// death, possibly at a known time.
+    Kill {
+        killer: Killer,
+        target: PlayerId,
+        means: MeansOfDeath,
+        time: Option<Timestamp>,
+    },
+    /// The player with the given ID gained an award, possibly at a known time.
+    Award { id: PlayerId, award: Award, time: Option<Timestamp> },
+    /// The player with the given ID touched the flag of the given team,
+    /// possibly at a known time.
+    FlagTouch { id: PlayerId, flag: Team, time: Option<Timestamp> },
+    /// The player with the given ID did something to the flag of the given
+    /// team, possibly at a known time.
+    Flag {
+        id: PlayerId,
+        flag: Team,
+        action: FlagAction,
+        time: Option<Timestamp>,
+    },
 }
 
 /// State of the parser.
@@ -188,8 +567,9 @@ enum Event {
 enum State {
     /// No game currently active.
     NoGame,
-    /// A game is currently active and will be eventually yielded.
-    InGame(Game),
+    /// A game is currently active and will be eventually yielded. Boxed since
+    /// a game is much larger than the other variants.
+    InGame(Box<Game>),
 }
 
 impl Default for State {
@@ -200,18 +580,80 @@ impl Default for State {
 }
 
 impl State {
-    /// Processes a line from the file, reacting to it.
-    fn process_line(&mut self, line: &str) -> Option<Game> {
-        let event = RawEvent::from_line(line).and_then(RawEvent::parse)?;
+    /// Processes a line from the file, reacting to it. Lines are read in the
+    /// dialect of the current game. New games are of the given dialect, or of
+    /// the dialect detected from `InitGame` if none is given.
+    fn process_line(
+        &mut self,
+        line: &str,
+        dialect: Option<Dialect>,
+    ) -> Option<Game> {
+        let current_dialect = match self {
+            State::InGame(game) => game.dialect,
+            State::NoGame => dialect.unwrap_or_default(),
+        };
+        let event = RawEvent::from_line(line)?.parse(current_dialect)?;
         match event {
-            Event::Init => self.start_game(),
-            Event::Shutdown => self.finish_game(),
-            Event::PlayerNameChanged { id, name } => {
+            Event::Init { game_type_code, game_name, version, map, time } => {
+                let dialect = dialect.unwrap_or_else(|| {
+                    Dialect::detect(game_name.as_deref(), version.as_deref())
+                });
+                let game_type = game_type_code
+                    .map(|code| dialect.game_type(code))
+                    .unwrap_or_default();
+                self.start_game(Game {
+                    game_type,
+                    dialect,
+                    version,
+                    map,
+                    start_time: time.unwrap_or(0),
+                    ..Game::default()
+                })
+            },
+            Event::Shutdown { time } => {
+                if let State::InGame(game) = self {
+                    game.end_time =
+                        Some(time.unwrap_or_else(|| last_kill_time(game)));
+                }
+                self.finish_game()
+            },
+            Event::Connect { id, time } => {
+                self.push_event(GameEventKind::Connect { player: id }, time);
+                None
+            },
+            Event::Disconnect { id, time } => {
+                self.push_event(GameEventKind::Disconnect { player: id }, time);
+                None
+            },
+            Event::PlayerInfoChanged { id, name, team, time } => {
+                self.push_event(
+                    GameEventKind::PlayerInfoChanged {
+                        player: id,
+                        name: name.clone(),
+                        team,
+                    },
+                    time,
+                );
                 self.change_player_name(id, name);
+                if let Some(team) = team {
+                    self.change_player_team(id, team);
+                }
+                None
+            },
+            Event::Kill { killer, target, means, time } => {
+                self.kill(killer, target, means, time);
+                None
+            },
+            Event::Award { id, award, time } => {
+                self.gain_award(id, award, time);
+                None
+            },
+            Event::FlagTouch { id, flag, time } => {
+                self.touch_flag(id, flag, time);
                 None
             },
-            Event::Kill { killer, target, means } => {
-                self.kill(killer, target, means);
+            Event::Flag { id, flag, action, time } => {
+                self.act_on_flag(id, flag, action, time);
                 None
             },
         }
@@ -221,19 +663,29 @@ impl State {
     /// the state to `NoGame`.
     fn finish_game(&mut self) -> Option<Game> {
         match mem::take(self) {
-            State::InGame(game) => Some(game),
+            State::InGame(game) => Some(*game),
             State::NoGame => None,
         }
     }
 
-    /// Starts a new empty game as the current state. Finishes a possible active
-    /// game and returns it (if any).
-    fn start_game(&mut self) -> Option<Game> {
+    /// Starts the given new game, with only its settings filled, as the
+    /// current state. Finishes a possible active game and returns it (if
+    /// any).
+    fn start_game(&mut self, game: Game) -> Option<Game> {
         let maybe_game = self.finish_game();
-        *self = State::InGame(Game::default());
+        *self = State::InGame(Box::new(game));
         maybe_game
     }
 
+    /// Pushes an event into the ordered event list of the game. If the time of
+    /// the event is unknown, the time of the last kill is used.
+    fn push_event(&mut self, kind: GameEventKind, time: Option<Timestamp>) {
+        if let State::InGame(game) = self {
+            let time = time.unwrap_or_else(|| last_kill_time(game));
+            game.events.push(GameEvent { kind, time });
+        }
+    }
+
     /// Reacts to the event of a player changing its name. If the player was not
     /// accounted yet, an entry for them will be created.
     fn change_player_name(&mut self, id: PlayerId, name: PlayerName) {
@@ -249,10 +701,142 @@ impl State {
         }
     }
 
-    /// Reacts to a `Kill` event by pushing it into the kill list.
-    fn kill(&mut self, killer: Killer, target: PlayerId, means: MeansOfDeath) {
+    /// Reacts to the event of a player changing their team.
+    fn change_player_team(&mut self, id: PlayerId, team: Team) {
+        if let State::InGame(game) = self {
+            game.teams.insert(id, team);
+        }
+    }
+
+    /// Reacts to a `Kill` event by pushing it into the kill list and the event
+    /// list. If the time of the event is unknown, the time of the previous
+    /// kill is used. If the target was carrying a flag, the flag is dropped.
+    fn kill(
+        &mut self,
+        killer: Killer,
+        target: PlayerId,
+        means: MeansOfDeath,
+        time: Option<Timestamp>,
+    ) {
         if let State::InGame(game) = self {
-            game.kills.push(Kill { killer, target, means });
+            let time = time.unwrap_or_else(|| last_kill_time(game));
+            for flag in [Team::Red, Team::Blue] {
+                if game.flag_carrier(flag) == Some(target) {
+                    game.flag_events.push(FlagEvent {
+                        flag,
+                        action: FlagAction::Drop { killer: Some(killer) },
+                        player: target,
+                        time,
+                    });
+                }
+            }
+            let kind = GameEventKind::Kill { index: game.kills.len() };
+            game.events.push(GameEvent { kind, time });
+            game.kills.push(Kill { killer, target, means, time });
         }
     }
+
+    /// Reacts to an `Award` event by pushing it into the player's award list.
+    /// If the time of the event is unknown, the time of the last kill is used.
+    fn gain_award(
+        &mut self,
+        id: PlayerId,
+        award: Award,
+        time: Option<Timestamp>,
+    ) {
+        if let State::InGame(game) = self {
+            let time = time.unwrap_or_else(|| last_kill_time(game));
+            let gained_award = GainedAward { award, time };
+            game.awards.entry(id).or_default().push(gained_award);
+        }
+    }
+
+    /// Reacts to a player touching a team flag. Touching the enemy flag picks
+    /// it up. Touching their own flag returns it if it was dropped, even while
+    /// carrying the enemy flag, which is kept. Otherwise, their own flag is at
+    /// its base, so touching it while carrying the enemy flag captures it
+    /// (the game only lets players touch their flag at the base when
+    /// capturing). Touches by
+    /// players without a red or blue team are ignored. If the time of the
+    /// event is unknown, the time of the last kill is used.
+    ///
+    /// OpenArena has dedicated events for all flag actions, so touches are
+    /// ignored, and Urban Terror has dedicated events for every action but
+    /// pickups.
+    fn touch_flag(
+        &mut self,
+        id: PlayerId,
+        flag: Team,
+        time: Option<Timestamp>,
+    ) {
+        if let State::InGame(game) = self {
+            let Some(team) = game.teams.get(&id).copied() else {
+                return;
+            };
+            let Some(enemy) = team.opponent() else {
+                return;
+            };
+            let own_flag_dropped = game
+                .flag_events
+                .iter()
+                .rev()
+                .find(|event| event.flag == team)
+                .is_some_and(|event| {
+                    matches!(event.action, FlagAction::Drop { .. })
+                });
+            let (flag, action) = if flag == enemy {
+                (enemy, FlagAction::Pickup)
+            } else if own_flag_dropped {
+                (team, FlagAction::Return)
+            } else if game.flag_carrier(enemy) == Some(id) {
+                (enemy, FlagAction::Capture)
+            } else {
+                (team, FlagAction::Return)
+            };
+            let dedicated_event = match game.dialect {
+                Dialect::Baseq3 | Dialect::MissionPack => false,
+                Dialect::OpenArena => true,
+                Dialect::UrbanTerror => action != FlagAction::Pickup,
+            };
+            if !dedicated_event {
+                let time = time.unwrap_or_else(|| last_kill_time(game));
+                let event = FlagEvent { flag, action, player: id, time };
+                game.flag_events.push(event);
+            }
+        }
+    }
+
+    /// Reacts to a dedicated flag event. A capture is of the flag the player
+    /// carries, if known. Drops are ignored unless the player carries the
+    /// flag, since the `Kill` event of a carrier already drops it. If the time
+    /// of the event is unknown, the time of the last kill is used.
+    fn act_on_flag(
+        &mut self,
+        id: PlayerId,
+        flag: Team,
+        action: FlagAction,
+        time: Option<Timestamp>,
+    ) {
+        if let State::InGame(game) = self {
+            let carried_flag = [Team::Red, Team::Blue]
+                .into_iter()
+                .find(|&flag| game.flag_carrier(flag) == Some(id));
+            let flag = match action {
+                FlagAction::Capture => carried_flag.unwrap_or(flag),
+                FlagAction::Drop { .. } => match carried_flag {
+                    Some(carried_flag) => carried_flag,
+                    None => return,
+                },
+                FlagAction::Pickup | FlagAction::Return => flag,
+            };
+            let time = time.unwrap_or_else(|| last_kill_time(game));
+            game.flag_events.push(FlagEvent { flag, action, player: id, time });
+        }
+    }
+}
+
+/// Time of the last kill of the game, used as the time of events whose time
+/// is unknown. If there are no kills, this is `0`.
+fn last_kill_time(game: &Game) -> Timestamp {
+    game.kills.last().map_or(0, |last_kill| last_kill.time)
 }
@@ -0,0 +1,51 @@
+//! This module exposes checkpoints of a parser, allowing parsing to be resumed
+//! later from where it stopped, e.g. in the next run of an incremental ingest.
+
+use super::follow::FileIdentity;
+use crate::game::{Game, GameCount};
+
+#[cfg(test)]
+mod test;
+
+/// Offset in bytes from the start of a log.
+pub type ByteOffset = u64;
+
+/// Position of a parser in a log and its in-progress state. Serializable, so
+/// it can be saved between runs.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    Default,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct Checkpoint {
+    /// Offset of the first byte of the log not processed yet, always at the
+    /// start of a line.
+    pub offset: ByteOffset,
+    /// The unfinished game at the offset, if any.
+    pub game: Option<Game>,
+    /// How many games were yielded before the offset, since the start of the
+    /// log.
+    pub games: GameCount,
+    /// Identity of the log file, if known, to detect that the log was rotated
+    /// since the checkpoint.
+    #[serde(default)]
+    pub file: Option<FileIdentity>,
+    /// Length of the log when the checkpoint was taken, at least the offset,
+    /// to detect that the log was truncated since the checkpoint.
+    #[serde(default)]
+    pub length: ByteOffset,
+}
+
+impl Checkpoint {
+    /// Checkpoint at the start of a new log, replacing the log of this
+    /// checkpoint, e.g. after a rotation. The unfinished game is dropped, but
+    /// the count of games is kept, so that games of the new log are numbered
+    /// after the previous ones.
+    pub fn restart(self) -> Self {
+        Self { games: self.games, ..Self::default() }
+    }
+}
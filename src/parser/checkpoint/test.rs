@@ -0,0 +1,149 @@
+use super::Checkpoint;
+use crate::{error::Result, game::Game, parser::Parser};
+use std::io::Cursor;
+
+const LOG: &str = concat!(
+    "  0:00 InitGame: \\g_gametype\\0\\gamename\\baseq3\n",
+    "  0:01 ClientConnect: 2\n",
+    "  0:01 ClientUserinfoChanged: 2 n\\Isgalamido\\t\\0\n",
+    "  0:02 ClientConnect: 3\n",
+    "  0:02 ClientUserinfoChanged: 3 n\\Zeh\\t\\0\n",
+    "  0:05 Kill: 2 3 7: Isgalamido killed Zeh by MOD_ROCKET_SPLASH\n",
+    "  1:00 ShutdownGame:\n",
+    "  1:01 InitGame: \\g_gametype\\0\\gamename\\baseq3\n",
+    "  1:02 ClientUserinfoChanged: 2 n\\Isgalamido\\t\\0\n",
+    "  1:03 ClientUserinfoChanged: 3 n\\Zeh\\t\\0\n",
+    "  1:04 Kill: 3 2 10: Zeh killed Isgalamido by MOD_RAILGUN\n",
+    "  1:09 Kill: 1022 3 22: <world> killed Zeh by MOD_TRIGGER_HURT\n",
+    "  2:00 ShutdownGame:\n",
+    "  2:01 InitGame: \\g_gametype\\0\\gamename\\baseq3\n",
+    "  2:02 ClientUserinfoChanged: 2 n\\Isgalamido\\t\\0\n",
+    "  2:03 ShutdownGame:\n",
+);
+
+fn all_games() -> Vec<Game> {
+    let result: Result<Vec<_>> = Parser::new(LOG.as_bytes()).collect();
+    result.unwrap()
+}
+
+/// Parses the log up to the given length incrementally, then resumes from
+/// the checkpoint over the full log.
+fn parse_in_two_runs(length: usize) -> (Checkpoint, Vec<Game>, Vec<Game>) {
+    let mut parser = Parser::new(&LOG.as_bytes()[.. length]).incremental();
+    let first: Result<Vec<_>> = parser.by_ref().collect();
+    let checkpoint = parser.checkpoint();
+
+    let reader = Cursor::new(LOG.as_bytes());
+    let parser = Parser::resume(reader, checkpoint.clone()).unwrap();
+    let second: Result<Vec<_>> = parser.collect();
+    (checkpoint, first.unwrap(), second.unwrap())
+}
+
+#[test]
+fn resume_at_any_point() {
+    let expected = all_games();
+    for length in 0 ..= LOG.len() {
+        let (checkpoint, first, second) = parse_in_two_runs(length);
+        let line_start = LOG[.. length].rfind('\n').map_or(0, |i| i + 1);
+        assert_eq!(checkpoint.offset, line_start as u64);
+        assert_eq!(checkpoint.games, first.len() as u64);
+        let games: Vec<_> = first.into_iter().chain(second).collect();
+        assert_eq!(expected, games, "split at {}", length);
+    }
+}
+
+#[test]
+fn checkpoint_unfinished_game() {
+    let length = LOG.find("  1:09").unwrap();
+    let (checkpoint, first, _) = parse_in_two_runs(length);
+    assert_eq!(first, &all_games()[.. 1]);
+    let game = checkpoint.game.unwrap();
+    assert_eq!(game.start_time, 61);
+    assert_eq!(game.kills.len(), 1);
+}
+
+#[test]
+fn incremental_end_of_log() {
+    let checkpoint = {
+        let mut parser = Parser::new(LOG.as_bytes()).incremental();
+        assert_eq!(parser.by_ref().count(), 3);
+        parser.checkpoint()
+    };
+    let expected = Checkpoint {
+        offset: LOG.len() as u64,
+        game: None,
+        games: 3,
+        file: None,
+        length: LOG.len() as u64,
+    };
+    assert_eq!(checkpoint, expected);
+
+    let parser = Parser::resume(Cursor::new(LOG.as_bytes()), checkpoint);
+    assert_eq!(parser.unwrap().count(), 0);
+}
+
+#[test]
+fn resume_truncated_log() {
+    let checkpoint = {
+        let mut parser = Parser::new(LOG.as_bytes()).incremental();
+        assert_eq!(parser.by_ref().count(), 3);
+        parser.checkpoint()
+    };
+
+    let truncated = &LOG[LOG.find("  2:01").unwrap() ..];
+    let parser = Parser::resume(Cursor::new(truncated.as_bytes()), checkpoint);
+    let mut parser = parser.unwrap();
+    let games: Result<Vec<_>> = parser.by_ref().collect();
+    assert_eq!(games.unwrap(), &all_games()[2 ..]);
+    assert_eq!(parser.checkpoint().games, 4);
+}
+
+#[cfg(unix)]
+#[test]
+fn resume_rotated_log() {
+    use std::{
+        env,
+        fs::{self, File},
+        process,
+    };
+
+    let dir = env::temp_dir().join(format!(
+        "quake-log-parser-checkpoint-{}",
+        process::id()
+    ));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("games.log");
+    let length = LOG.find("  2:01").unwrap();
+    fs::write(&path, &LOG[.. length]).unwrap();
+    let checkpoint = {
+        let mut parser = Parser::new(File::open(&path).unwrap()).incremental();
+        assert_eq!(parser.by_ref().count(), 2);
+        parser.file_checkpoint().unwrap()
+    };
+    assert!(checkpoint.file.is_some());
+    assert_eq!(checkpoint.length, length as u64);
+
+    let rotated = dir.join("games.log.1");
+    fs::rename(&path, &rotated).unwrap();
+    fs::write(&path, LOG).unwrap();
+    let file = File::open(&path).unwrap();
+    let mut parser = Parser::resume_file(file, checkpoint.clone()).unwrap();
+    let games: Result<Vec<_>> = parser.by_ref().collect();
+    assert_eq!(games.unwrap(), all_games());
+    assert_eq!(parser.checkpoint().games, 5);
+
+    let file = File::open(&rotated).unwrap();
+    let parser = Parser::resume_file(file, checkpoint).unwrap();
+    assert_eq!(parser.count(), 0);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn serialize_checkpoint() {
+    let length = LOG.find("  1:09").unwrap();
+    let (checkpoint, _, _) = parse_in_two_runs(length);
+    let json = serde_json::to_string(&checkpoint).unwrap();
+    let deserialized: Checkpoint = serde_json::from_str(&json).unwrap();
+    assert_eq!(checkpoint, deserialized);
+}
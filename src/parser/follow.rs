@@ -0,0 +1,169 @@
+//! This module exposes a reader that follows a live log file, like `tail -F`:
+//! instead of reaching the end of the file, it waits for more data, and it
+//! keeps following the log through truncation and rotation.
+
+use std::{
+    fs::{self, File, Metadata},
+    io::{self, Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+    thread,
+    time::Duration,
+};
+
+#[cfg(test)]
+mod test;
+
+/// Identity of a file in the file system, used to detect that a path was
+/// rotated, i.e. now refers to another file.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct FileIdentity {
+    /// Device of the file.
+    device: u64,
+    /// Inode of the file.
+    inode: u64,
+}
+
+impl FileIdentity {
+    /// Identity of the file with the given metadata. Only available on Unix;
+    /// elsewhere, rotation is only detected as a truncation.
+    pub fn of(metadata: &Metadata) -> Option<Self> {
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            Some(Self { device: metadata.dev(), inode: metadata.ino() })
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = metadata;
+            None
+        }
+    }
+}
+
+/// What happened to the followed path since it was opened.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Change {
+    /// Nothing, or the path is missing while the log is being rotated.
+    None,
+    /// The file was truncated, e.g. by a `copytruncate` rotation.
+    Truncated,
+    /// The path now refers to a new file.
+    Rotated,
+}
+
+/// A reader that never reaches the end of a log file: at the end, it waits
+/// for the server to write more data, polling the file periodically. If the
+/// file is truncated, it is read again from its start; if the path is rotated
+/// to a new file, the old file is read until its end and then the new one is
+/// read from its start.
+#[derive(Debug)]
+pub struct Follower {
+    /// Path of the followed log file.
+    path: PathBuf,
+    /// The file currently being read.
+    file: File,
+    /// Identity of the file currently being read, if available.
+    identity: Option<FileIdentity>,
+    /// How many bytes were read from the current file.
+    position: u64,
+    /// How long to wait before polling the file again at its end.
+    poll_interval: Duration,
+}
+
+impl Follower {
+    /// Default time waited before polling the file again at its end.
+    pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+    /// Opens the log file at the given path, to be followed from its start.
+    pub fn new<P>(path: P) -> io::Result<Self>
+    where
+        P: Into<PathBuf>,
+    {
+        let path = path.into();
+        let (file, identity) = Self::open(&path)?;
+        Ok(Self {
+            path,
+            file,
+            identity,
+            position: 0,
+            poll_interval: Self::DEFAULT_POLL_INTERVAL,
+        })
+    }
+
+    /// Sets how long to wait before polling the file again at its end.
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Opens the file at the given path, along with its identity.
+    fn open(path: &Path) -> io::Result<(File, Option<FileIdentity>)> {
+        let file = File::open(path).map_err(|error| {
+            io::Error::new(
+                error.kind(),
+                format!("Failed to open {}: {}", path.display(), error),
+            )
+        })?;
+        let identity = FileIdentity::of(&file.metadata()?);
+        Ok((file, identity))
+    }
+
+    /// Checks what happened to the followed path since it was opened.
+    fn change(&self) -> io::Result<Change> {
+        let metadata = match fs::metadata(&self.path) {
+            Ok(metadata) => metadata,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => {
+                return Ok(Change::None)
+            },
+            Err(error) => return Err(error),
+        };
+        if FileIdentity::of(&metadata) != self.identity {
+            Ok(Change::Rotated)
+        } else if metadata.len() < self.position {
+            Ok(Change::Truncated)
+        } else {
+            Ok(Change::None)
+        }
+    }
+}
+
+impl Read for Follower {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        loop {
+            let count = self.file.read(buf)?;
+            if count > 0 {
+                self.position += count as u64;
+                return Ok(count);
+            }
+            match self.change()? {
+                Change::None => thread::sleep(self.poll_interval),
+                Change::Truncated => {
+                    log::info!("{} was truncated", self.path.display());
+                    self.file.seek(SeekFrom::Start(0))?;
+                    self.position = 0;
+                },
+                Change::Rotated => {
+                    log::info!("{} was rotated", self.path.display());
+                    let count = self.file.read(buf)?;
+                    if count > 0 {
+                        self.position += count as u64;
+                        return Ok(count);
+                    }
+                    (self.file, self.identity) = Self::open(&self.path)?;
+                    self.position = 0;
+                },
+            }
+        }
+    }
+}
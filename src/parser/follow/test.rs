@@ -0,0 +1,112 @@
+use super::Follower;
+use crate::parser::Parser;
+use std::{
+    env,
+    fs::{self, OpenOptions},
+    io::{BufReader, Write},
+    path::{Path, PathBuf},
+    process,
+    thread,
+    time::Duration,
+};
+
+fn game_log(player: &str) -> String {
+    format!(
+        concat!(
+            "  0:00 InitGame: \\g_gametype\\0\n",
+            "  0:01 ClientUserinfoChanged: 2 n\\{}\\t\\0\n",
+            "  0:02 Kill: 1022 2 22: <world> killed {} by MOD_TRIGGER_HURT\n",
+            "  0:03 ShutdownGame:\n",
+        ),
+        player, player,
+    )
+}
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir()
+        .join(format!("quake-log-parser-{}-{}", name, process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn append(path: &Path, contents: &str) {
+    let mut file =
+        OpenOptions::new().append(true).create(true).open(path).unwrap();
+    file.write_all(contents.as_bytes()).unwrap();
+}
+
+fn follow(path: &Path) -> Parser<BufReader<Follower>> {
+    let follower = Follower::new(path)
+        .unwrap()
+        .with_poll_interval(Duration::from_millis(5));
+    Parser::new(follower)
+}
+
+fn next_player(parser: &mut Parser<BufReader<Follower>>) -> String {
+    let game = parser.next().unwrap().unwrap();
+    game.players.values().next().unwrap().clone()
+}
+
+#[test]
+fn wait_for_new_games() {
+    let dir = temp_dir("follow");
+    let path = dir.join("qgames.log");
+    append(&path, &game_log("Isgalamido"));
+    let mut parser = follow(&path);
+    assert_eq!(next_player(&mut parser), "Isgalamido");
+
+    let writer = {
+        let path = path.clone();
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            let log = game_log("Zeh");
+            let (first, second) = log.split_at(40);
+            append(&path, first);
+            thread::sleep(Duration::from_millis(20));
+            append(&path, second);
+        })
+    };
+    assert_eq!(next_player(&mut parser), "Zeh");
+    writer.join().unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn follow_truncation() {
+    let dir = temp_dir("truncate");
+    let path = dir.join("qgames.log");
+    append(&path, &game_log("Isgalamido"));
+    let mut parser = follow(&path);
+    assert_eq!(next_player(&mut parser), "Isgalamido");
+
+    fs::write(&path, game_log("Zeh")).unwrap();
+    assert_eq!(next_player(&mut parser), "Zeh");
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn follow_rotation() {
+    let dir = temp_dir("rotate");
+    let path = dir.join("qgames.log");
+    let log = game_log("Isgalamido");
+    let (first, second) = log.split_at(60);
+    append(&path, first);
+    let mut parser = follow(&path);
+
+    let writer = {
+        let path = path.clone();
+        let second = String::from(second);
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            append(&path, &second);
+            fs::rename(&path, path.with_extension("log.1")).unwrap();
+            thread::sleep(Duration::from_millis(20));
+            append(&path, &game_log("Zeh"));
+        })
+    };
+    assert_eq!(next_player(&mut parser), "Isgalamido");
+    assert_eq!(next_player(&mut parser), "Zeh");
+    writer.join().unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,266 @@
+//! This module exposes inputs made of several log files, such as a log and
+//! its rotated copies, read as one continuous stream, and transparent
+//! decompression of compressed logs.
+
+use std::{
+    cmp::Reverse,
+    collections::VecDeque,
+    fmt,
+    fs::File,
+    io::{self, BufRead, BufReader, Read},
+    path::{Path, PathBuf},
+};
+
+#[cfg(test)]
+mod test;
+
+/// Extensions of compressed files, ignored when looking for the rotation
+/// index of a file (e.g. `qgames.log.2.gz`).
+const COMPRESSED_EXTENSIONS: &[&str] = &["gz", "zst", "bz2"];
+
+/// Index of a rotated log file, e.g. `2` for `qgames.log.2`. The live log has
+/// index `0`, and older files have greater indices.
+pub type RotationIndex = u64;
+
+/// Compression format of a log, detected by the magic bytes at its start.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Compression {
+    /// Not compressed, i.e. plain text.
+    None,
+    /// gzip, decoded only with the `gzip` feature.
+    Gzip,
+    /// Zstandard, decoded only with the `zstd` feature.
+    Zstd,
+    /// bzip2, decoded only with the `bzip2` feature.
+    Bzip2,
+}
+
+impl Compression {
+    /// Detects the compression format from the first bytes of a log. Logs
+    /// whose start matches no known magic are plain text.
+    pub fn detect(start: &[u8]) -> Self {
+        if start.starts_with(&[0x1f, 0x8b]) {
+            Self::Gzip
+        } else if start.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+            Self::Zstd
+        } else if start.starts_with(b"BZh") {
+            Self::Bzip2
+        } else {
+            Self::None
+        }
+    }
+
+    /// Name of the compression format, also the name of the cargo feature
+    /// that enables decoding it.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Gzip => "gzip",
+            Self::Zstd => "zstd",
+            Self::Bzip2 => "bzip2",
+        }
+    }
+}
+
+/// The decoding reader of a log, according to its compression.
+enum DecoderInner<R> {
+    /// Plain text, read as is.
+    Plain(BufReader<R>),
+    /// gzip, with any number of members.
+    #[cfg(feature = "gzip")]
+    Gzip(flate2::bufread::MultiGzDecoder<BufReader<R>>),
+    /// Zstandard, with any number of frames.
+    #[cfg(feature = "zstd")]
+    Zstd(zstd::stream::read::Decoder<'static, BufReader<R>>),
+    /// bzip2, with any number of streams.
+    #[cfg(feature = "bzip2")]
+    Bzip2(bzip2::bufread::MultiBzDecoder<BufReader<R>>),
+}
+
+/// A reader that decompresses a log on the fly, if it is compressed. The
+/// compression is detected by magic bytes, not by file extension.
+pub struct Decoder<R> {
+    /// Compression detected at the start of the log.
+    compression: Compression,
+    /// The decoding reader.
+    inner: DecoderInner<R>,
+}
+
+impl<R> Decoder<R>
+where
+    R: Read,
+{
+    /// Creates the decoder from a reader object, peeking its first bytes to
+    /// detect the compression. Fails if the log is compressed in a format
+    /// whose feature is not enabled.
+    pub fn new(reader: R) -> io::Result<Self> {
+        let mut reader = BufReader::new(reader);
+        let compression = Compression::detect(reader.fill_buf()?);
+        let inner = match compression {
+            Compression::None => DecoderInner::Plain(reader),
+            #[cfg(feature = "gzip")]
+            Compression::Gzip => DecoderInner::Gzip(
+                flate2::bufread::MultiGzDecoder::new(reader),
+            ),
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => DecoderInner::Zstd(
+                zstd::stream::read::Decoder::with_buffer(reader)?,
+            ),
+            #[cfg(feature = "bzip2")]
+            Compression::Bzip2 => DecoderInner::Bzip2(
+                bzip2::bufread::MultiBzDecoder::new(reader),
+            ),
+            #[allow(unreachable_patterns)]
+            unsupported => {
+                return Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    format!(
+                        "Log is {} compressed, but the {} feature is disabled",
+                        unsupported.name(),
+                        unsupported.name(),
+                    ),
+                ))
+            },
+        };
+        Ok(Self { compression, inner })
+    }
+}
+
+impl<R> Decoder<R> {
+    /// Compression detected at the start of the log.
+    pub fn compression(&self) -> Compression {
+        self.compression
+    }
+}
+
+impl<R> Read for Decoder<R>
+where
+    R: Read,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match &mut self.inner {
+            DecoderInner::Plain(reader) => reader.read(buf),
+            #[cfg(feature = "gzip")]
+            DecoderInner::Gzip(reader) => reader.read(buf),
+            #[cfg(feature = "zstd")]
+            DecoderInner::Zstd(reader) => reader.read(buf),
+            #[cfg(feature = "bzip2")]
+            DecoderInner::Bzip2(reader) => reader.read(buf),
+        }
+    }
+}
+
+impl<R> fmt::Debug for Decoder<R> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("Decoder")
+            .field("compression", &self.compression)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A reader over an ordered list of log files, read one after the other as if
+/// they were a single file. Files are opened only when the previous one is
+/// exhausted, and nothing is inserted between them, so a line (and thus a
+/// game) split across a rotation boundary is joined back. Compressed files are
+/// decompressed on the fly.
+#[derive(Debug)]
+pub struct LogFiles {
+    /// Paths of files not opened yet, in reading order.
+    paths: VecDeque<PathBuf>,
+    /// The file currently being read and its path, if any.
+    current: Option<(PathBuf, Decoder<File>)>,
+}
+
+impl LogFiles {
+    /// Creates the reader from the paths of the files, in reading order.
+    pub fn new<I, P>(paths: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<PathBuf>,
+    {
+        let paths = paths.into_iter().map(Into::into).collect();
+        Self { paths, current: None }
+    }
+
+    /// Opens the next file, returning whether there was a next file.
+    fn open_next(&mut self) -> io::Result<bool> {
+        let Some(path) = self.paths.pop_front() else {
+            return Ok(false);
+        };
+        let file = File::open(&path)
+            .and_then(Decoder::new)
+            .map_err(|error| file_error(&path, "open", error))?;
+        self.current = Some((path, file));
+        Ok(true)
+    }
+}
+
+/// Adds the path of a file to an error that happened while accessing it.
+fn file_error(path: &Path, action: &str, error: io::Error) -> io::Error {
+    io::Error::new(
+        error.kind(),
+        format!("Failed to {} {}: {}", action, path.display(), error),
+    )
+}
+
+impl Read for LogFiles {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        loop {
+            let Some((path, file)) = &mut self.current else {
+                if self.open_next()? {
+                    continue;
+                }
+                return Ok(0);
+            };
+            let result = file.read(buf);
+            match result.map_err(|error| file_error(path, "read", error))? {
+                0 => self.current = None,
+                count => return Ok(count),
+            }
+        }
+    }
+}
+
+/// Rotation index of the given log file path, e.g. `2` for `qgames.log.2` and
+/// `qgames.log.2.gz`, and `0` for `qgames.log`.
+pub fn rotation_index(path: &Path) -> RotationIndex {
+    rotation_base(path).1
+}
+
+/// Splits the path into the path of the live log it was rotated from and its
+/// rotation index.
+fn rotation_base(path: &Path) -> (PathBuf, RotationIndex) {
+    let mut base = path.to_path_buf();
+    let is_compressed = base
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| COMPRESSED_EXTENSIONS.contains(&extension));
+    if is_compressed {
+        base.set_extension("");
+    }
+    let index = base
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .and_then(|extension| extension.parse().ok());
+    match index {
+        Some(index) => {
+            base.set_extension("");
+            (base, index)
+        },
+        None => (base, 0),
+    }
+}
+
+/// Sorts log file paths in the order they were written: files rotated from
+/// the same live log go from the oldest (greatest rotation index) to the live
+/// log itself, while distinct live logs are sorted by path.
+pub fn sort_by_rotation(paths: &mut [PathBuf]) {
+    paths.sort_by_cached_key(|path| {
+        let (base, index) = rotation_base(path);
+        (base, Reverse(index), path.clone())
+    });
+}
@@ -0,0 +1,141 @@
+use super::{rotation_index, sort_by_rotation, Compression, Decoder, LogFiles};
+use std::{
+    env,
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    process,
+};
+
+const PLAIN_LOG: &str = concat!(
+    "  0:00 InitGame: \\g_gametype\\0\n",
+    "  1:00 ShutdownGame:\n",
+);
+
+#[test]
+fn rotation_indices() {
+    assert_eq!(rotation_index(Path::new("qgames.log")), 0);
+    assert_eq!(rotation_index(Path::new("qgames.log.1")), 1);
+    assert_eq!(rotation_index(Path::new("logs/qgames.log.12.gz")), 12);
+    assert_eq!(rotation_index(Path::new("qgames.log.gz")), 0);
+}
+
+#[test]
+fn sort_rotated_files() {
+    let mut paths: Vec<_> = [
+        "qgames.log",
+        "other.log.1",
+        "qgames.log.10.gz",
+        "qgames.log.1",
+        "qgames.log.2.gz",
+        "other.log",
+    ]
+    .into_iter()
+    .map(PathBuf::from)
+    .collect();
+    sort_by_rotation(&mut paths);
+    let expected = [
+        "other.log.1",
+        "other.log",
+        "qgames.log.10.gz",
+        "qgames.log.2.gz",
+        "qgames.log.1",
+        "qgames.log",
+    ];
+    assert_eq!(paths, expected.map(PathBuf::from));
+}
+
+#[test]
+fn read_files_as_one_stream() {
+    let dir = env::temp_dir()
+        .join(format!("quake-log-parser-input-{}", process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let paths = [dir.join("qgames.log.1"), dir.join("qgames.log")];
+    let (older, newer) = PLAIN_LOG.split_at(26);
+    fs::write(&paths[0], older).unwrap();
+    fs::write(&paths[1], newer).unwrap();
+
+    let mut contents = String::new();
+    let result = LogFiles::new(&paths).read_to_string(&mut contents);
+    fs::remove_dir_all(&dir).unwrap();
+    result.unwrap();
+
+    assert_eq!(contents, PLAIN_LOG);
+}
+
+#[test]
+fn read_missing_file() {
+    let path = env::temp_dir().join("quake-log-parser-missing.log");
+    let mut contents = String::new();
+    let error =
+        LogFiles::new([&path]).read_to_string(&mut contents).unwrap_err();
+    assert!(error.to_string().contains("quake-log-parser-missing.log"));
+}
+
+fn decode(data: &[u8]) -> io::Result<(Compression, String)> {
+    let mut decoder = Decoder::new(data)?;
+    let mut contents = String::new();
+    decoder.read_to_string(&mut contents)?;
+    Ok((decoder.compression(), contents))
+}
+
+#[test]
+fn detect_compression() {
+    assert_eq!(Compression::detect(PLAIN_LOG.as_bytes()), Compression::None);
+    assert_eq!(Compression::detect(b""), Compression::None);
+    assert_eq!(Compression::detect(&[0x1f, 0x8b, 8]), Compression::Gzip);
+    let zstd_magic = [0x28, 0xb5, 0x2f, 0xfd];
+    assert_eq!(Compression::detect(&zstd_magic), Compression::Zstd);
+    assert_eq!(Compression::detect(b"BZh91AY"), Compression::Bzip2);
+}
+
+#[test]
+fn decode_plain() {
+    let decoded = decode(PLAIN_LOG.as_bytes()).unwrap();
+    assert_eq!(decoded, (Compression::None, String::from(PLAIN_LOG)));
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn decode_gzip() {
+    use flate2::{write::GzEncoder, Compression as Level};
+    use std::io::Write;
+
+    let mut data = Vec::new();
+    for half in [&PLAIN_LOG[.. 10], &PLAIN_LOG[10 ..]] {
+        let mut encoder = GzEncoder::new(Vec::new(), Level::default());
+        encoder.write_all(half.as_bytes()).unwrap();
+        data.extend(encoder.finish().unwrap());
+    }
+    let decoded = decode(&data).unwrap();
+    assert_eq!(decoded, (Compression::Gzip, String::from(PLAIN_LOG)));
+}
+
+#[cfg(feature = "zstd")]
+#[test]
+fn decode_zstd() {
+    let data = zstd::encode_all(PLAIN_LOG.as_bytes(), 0).unwrap();
+    let decoded = decode(&data).unwrap();
+    assert_eq!(decoded, (Compression::Zstd, String::from(PLAIN_LOG)));
+}
+
+#[cfg(feature = "bzip2")]
+#[test]
+fn decode_bzip2() {
+    use bzip2::{write::BzEncoder, Compression as Level};
+    use std::io::Write;
+
+    let mut encoder = BzEncoder::new(Vec::new(), Level::default());
+    encoder.write_all(PLAIN_LOG.as_bytes()).unwrap();
+    let data = encoder.finish().unwrap();
+    let decoded = decode(&data).unwrap();
+    assert_eq!(decoded, (Compression::Bzip2, String::from(PLAIN_LOG)));
+}
+
+#[cfg(not(feature = "gzip"))]
+#[test]
+fn decode_gzip_disabled() {
+    let error = decode(&[0x1f, 0x8b, 8, 0]).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::Unsupported);
+    assert!(error.to_string().contains("gzip feature"));
+}
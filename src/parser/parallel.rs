@@ -0,0 +1,156 @@
+//! This module exposes parallel parsing of big log files: the log is split
+//! into chunks that start at `InitGame` events, and the chunks are parsed on
+//! a thread pool. Since games are independent once their boundaries are
+//! known, the games are the same, and in the same order, as if the log was
+//! parsed sequentially.
+
+use super::{input::Compression, Parser, RawEvent};
+use crate::{
+    error::Result,
+    game::{Dialect, Game},
+};
+use anyhow::{bail, Context};
+use memmap2::Mmap;
+use rayon::prelude::*;
+use std::{fs::File, path::Path};
+
+#[cfg(test)]
+mod test;
+
+/// Parallel parser of Quake III: Arena log data fully available at once, such
+/// as a memory-mapped file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParallelParser {
+    /// Dialect of the games in the log, detected for each game if not set.
+    dialect: Option<Dialect>,
+    /// Minimum size in bytes of a chunk, unless it is the last one.
+    chunk_size: usize,
+}
+
+impl Default for ParallelParser {
+    fn default() -> Self {
+        Self { dialect: None, chunk_size: Self::DEFAULT_CHUNK_SIZE }
+    }
+}
+
+impl ParallelParser {
+    /// Default minimum size of a chunk: 4 MiB.
+    pub const DEFAULT_CHUNK_SIZE: usize = 4 << 20;
+
+    /// Creates the parser with the default configuration.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the dialect of all games in the log. By default, the dialect is
+    /// detected for each game from its `InitGame` event.
+    pub fn with_dialect(mut self, dialect: Dialect) -> Self {
+        self.dialect = Some(dialect);
+        self
+    }
+
+    /// Sets the minimum size in bytes of a chunk. Smaller chunks spread the
+    /// work better, at the cost of more tasks.
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    /// Parses the plain (not compressed) log file at the given path, mapping
+    /// it into memory. The file must not be modified while it is parsed.
+    pub fn parse_file<P>(&self, path: P) -> Result<Vec<Game>>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let file = File::open(path)
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+        // SAFETY: the mapping is only read, and it is only valid as long as
+        // the file is not truncated or modified, as documented above.
+        let map = unsafe { Mmap::map(&file) }
+            .with_context(|| format!("Failed to map {}", path.display()))?;
+        let compression = Compression::detect(&map);
+        if compression != Compression::None {
+            bail!(
+                "{} is {} compressed and cannot be parsed in parallel",
+                path.display(),
+                compression.name(),
+            );
+        }
+        self.parse_bytes(&map)
+    }
+
+    /// Parses the given log data, returning its games in log order.
+    pub fn parse_bytes(&self, data: &[u8]) -> Result<Vec<Game>> {
+        let chunks = self.split(data);
+        let parsed: Vec<Result<Vec<Game>>> = chunks
+            .into_par_iter()
+            .map(|chunk| {
+                let mut parser = Parser::with_bufread(chunk);
+                if let Some(dialect) = self.dialect {
+                    parser = parser.with_dialect(dialect);
+                }
+                parser.collect()
+            })
+            .collect();
+
+        let mut games = Vec::new();
+        for result in parsed {
+            games.extend(result?);
+        }
+        Ok(games)
+    }
+
+    /// Splits the log data into chunks of at least the chunk size, all of
+    /// them but the first starting at a line with an `InitGame` event.
+    fn split<'data>(&self, data: &'data [u8]) -> Vec<&'data [u8]> {
+        let mut chunks = Vec::new();
+        let mut start = 0;
+        while start < data.len() {
+            let offset = start.saturating_add(self.chunk_size);
+            let end = next_game_start(data, offset);
+            chunks.push(&data[start .. end]);
+            start = end;
+        }
+        chunks
+    }
+}
+
+/// Finds the start of the first line at or after the given offset with an
+/// `InitGame` event, or the end of the data if there is none. The offset
+/// itself only counts if it is the start of a line.
+fn next_game_start(data: &[u8], offset: usize) -> usize {
+    if offset >= data.len() {
+        return data.len();
+    }
+    let mut line_start = if offset == 0 || data[offset - 1] == b'\n' {
+        offset
+    } else {
+        line_end(data, offset)
+    };
+    while line_start < data.len() {
+        let end = line_end(data, line_start);
+        if is_init_game(&data[line_start .. end]) {
+            return line_start;
+        }
+        line_start = end;
+    }
+    data.len()
+}
+
+/// Finds the end of the line containing the given offset, including its line
+/// feed.
+fn line_end(data: &[u8], offset: usize) -> usize {
+    data[offset ..]
+        .iter()
+        .position(|&byte| byte == b'\n')
+        .map_or(data.len(), |index| offset + index + 1)
+}
+
+/// Tests whether the given line has an `InitGame` event.
+fn is_init_game(line: &[u8]) -> bool {
+    std::str::from_utf8(line)
+        .ok()
+        .and_then(RawEvent::from_line)
+        .is_some_and(|event| event.key == "InitGame")
+}
@@ -0,0 +1,102 @@
+use super::{next_game_start, ParallelParser};
+use crate::{error::Result, game::Dialect, parser::Parser};
+use std::{env, fs, process};
+
+fn game_log(player: &str, game_type: u32, shutdown: bool) -> String {
+    let mut log = format!(
+        concat!(
+            "  0:00 ------------------------------\n",
+            "  0:00 InitGame: \\g_gametype\\{}\\gamename\\baseq3\n",
+            "  0:01 ClientConnect: 2\n",
+            "  0:01 ClientUserinfoChanged: 2 n\\{}\\t\\1\n",
+            "  0:02 ClientConnect: 3\n",
+            "  0:02 ClientUserinfoChanged: 3 n\\Zeh\\t\\2\n",
+            "  0:05 Kill: 2 3 7: {} killed Zeh by MOD_ROCKET_SPLASH\n",
+            "  0:09 Kill: 1022 2 22: <world> killed {} by MOD_TRIGGER_HURT\n",
+        ),
+        game_type, player, player, player,
+    );
+    if shutdown {
+        log.push_str("  1:00 ShutdownGame:\n");
+    }
+    log
+}
+
+fn big_log() -> String {
+    let mut log = String::from("  0:00 Kill: 1022 2 22: before any game\n");
+    for i in 0 .. 50 {
+        let player = format!("Player {}", i);
+        log.push_str(&game_log(&player, i % 5, i % 7 != 0));
+    }
+    log
+}
+
+#[test]
+fn find_game_starts() {
+    let log = big_log();
+    let data = log.as_bytes();
+    let first = log.find("  0:00 InitGame").unwrap();
+    assert_eq!(next_game_start(data, 0), first);
+    assert_eq!(next_game_start(data, 5), first);
+    assert_eq!(next_game_start(data, first), first);
+    let second = first + 1 + log[first + 1 ..].find("  0:00 InitGame").unwrap();
+    assert_eq!(next_game_start(data, first + 1), second);
+    assert_eq!(next_game_start(data, log.len() - 3), log.len());
+    assert_eq!(next_game_start(data, log.len() + 3), log.len());
+}
+
+#[test]
+fn same_games_as_sequential_parser() {
+    let log = big_log();
+    let expected: Result<Vec<_>> = Parser::new(log.as_bytes()).collect();
+    let expected = expected.unwrap();
+    assert_eq!(expected.len(), 50);
+
+    for chunk_size in [1, 100, 1000, 4096, log.len()] {
+        let parser = ParallelParser::new().with_chunk_size(chunk_size);
+        let actual = parser.parse_bytes(log.as_bytes()).unwrap();
+        assert_eq!(expected, actual, "chunk size {}", chunk_size);
+    }
+}
+
+#[test]
+fn forced_dialect() {
+    let log = big_log();
+    let games = ParallelParser::new()
+        .with_chunk_size(100)
+        .with_dialect(Dialect::OpenArena)
+        .parse_bytes(log.as_bytes())
+        .unwrap();
+    assert!(games.iter().all(|game| game.dialect == Dialect::OpenArena));
+}
+
+#[test]
+fn parse_mapped_file() {
+    let log = big_log();
+    let path = env::temp_dir()
+        .join(format!("quake-log-parser-parallel-{}.log", process::id()));
+    fs::write(&path, &log).unwrap();
+    let result = ParallelParser::new().with_chunk_size(512).parse_file(&path);
+    fs::remove_file(&path).unwrap();
+
+    let expected: Result<Vec<_>> = Parser::new(log.as_bytes()).collect();
+    assert_eq!(expected.unwrap(), result.unwrap());
+}
+
+#[test]
+fn compressed_file() {
+    let path = env::temp_dir()
+        .join(format!("quake-log-parser-parallel-{}.gz", process::id()));
+    fs::write(&path, [0x1f, 0x8b, 8, 0]).unwrap();
+    let result = ParallelParser::new().parse_file(&path);
+    fs::remove_file(&path).unwrap();
+    assert!(result.is_err());
+}
+
+#[test]
+fn invalid_utf8() {
+    let mut log = big_log().into_bytes();
+    log.extend(b"  2:00 Kill: \xff\n");
+    let parser = ParallelParser::new().with_chunk_size(100);
+    assert!(parser.parse_bytes(&log).is_err());
+}
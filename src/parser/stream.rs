@@ -0,0 +1,88 @@
+//! This module exposes an asynchronous Quake III: Arena log file parser, the
+//! counterpart of [`Parser`](super::Parser) over `tokio`'s asynchronous
+//! readers. It shares the parser state with the synchronous parser, so both
+//! yield the same games.
+
+use super::State;
+use crate::{
+    error::Result,
+    game::{Dialect, Game},
+};
+use futures_core::Stream;
+use std::{
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncRead, BufReader, Lines};
+
+#[cfg(test)]
+mod test;
+
+/// Asynchronous parser of Quake III: Arena log file. It works as a stream. It
+/// reads a file's line by line without blocking, and whenever a game is
+/// finished, it is yielded through the stream.
+#[derive(Debug)]
+pub struct AsyncParser<R> {
+    /// Lines of the log data.
+    lines: Lines<R>,
+    /// Parser state, fed with lines from the reader.
+    state: State,
+    /// Dialect of the games in the log, detected for each game if not set.
+    dialect: Option<Dialect>,
+}
+
+impl<R> AsyncParser<BufReader<R>>
+where
+    R: AsyncRead,
+{
+    /// Creates the parser from an asynchronous reader object (typically a
+    /// file or a socket), wrapping it with a buffered reader.
+    pub fn new(reader: R) -> Self {
+        Self::with_bufread(BufReader::new(reader))
+    }
+}
+
+impl<R> AsyncParser<R>
+where
+    R: AsyncBufRead,
+{
+    /// Creates the parser from an asynchronous buffered reader object.
+    pub fn with_bufread(reader: R) -> Self {
+        Self { lines: reader.lines(), state: State::NoGame, dialect: None }
+    }
+
+    /// Sets the dialect of all games in the log. By default, the dialect is
+    /// detected for each game from its `InitGame` event.
+    pub fn with_dialect(mut self, dialect: Dialect) -> Self {
+        self.dialect = Some(dialect);
+        self
+    }
+}
+
+impl<R> Stream for AsyncParser<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    type Item = Result<Game>;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        context: &mut Context,
+    ) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            let lines = Pin::new(&mut this.lines);
+            let line = match ready!(lines.poll_next_line(context)) {
+                Ok(Some(line)) => line,
+                Ok(None) => {
+                    return Poll::Ready(this.state.finish_game().map(Ok))
+                },
+                Err(error) => return Poll::Ready(Some(Err(error.into()))),
+            };
+
+            if let Some(game) = this.state.process_line(&line, this.dialect) {
+                return Poll::Ready(Some(Ok(game)));
+            }
+        }
+    }
+}
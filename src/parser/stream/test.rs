@@ -0,0 +1,68 @@
+use super::AsyncParser;
+use crate::{
+    error::Result,
+    game::{Dialect, Game},
+    parser::Parser,
+};
+use futures_core::Stream;
+use std::{
+    pin::pin,
+    task::{Context, Poll, Waker},
+};
+
+const LOG: &str = concat!(
+    "  0:00 InitGame: \\g_gametype\\0\\gamename\\baseq3\n",
+    "  0:01 ClientConnect: 2\n",
+    "  0:01 ClientUserinfoChanged: 2 n\\Isgalamido\\t\\0\n",
+    "  0:02 ClientConnect: 3\n",
+    "  0:02 ClientUserinfoChanged: 3 n\\Zeh\\t\\0\n",
+    "  0:05 Kill: 2 3 7: Isgalamido killed Zeh by MOD_ROCKET_SPLASH\n",
+    "  0:09 Kill: 1022 2 22: <world> killed Isgalamido by MOD_TRIGGER_HURT\n",
+    "  1:00 ShutdownGame:\n",
+    "  1:01 InitGame: \\g_gametype\\4\\gamename\\q3ut4\n",
+    "  1:02 ClientUserinfoChanged: 2 n\\Isgalamido\\t\\1\n",
+    "  1:04 Kill: 2 2 7: Isgalamido killed Isgalamido by MOD_SUICIDE\n",
+);
+
+/// Collects the games of a stream whose reader never blocks, such as a byte
+/// slice, so no runtime is needed.
+fn collect<S>(stream: S) -> Result<Vec<Game>>
+where
+    S: Stream<Item = Result<Game>>,
+{
+    let mut stream = pin!(stream);
+    let mut context = Context::from_waker(Waker::noop());
+    let mut games = Vec::new();
+    loop {
+        match stream.as_mut().poll_next(&mut context) {
+            Poll::Ready(Some(result)) => games.push(result?),
+            Poll::Ready(None) => return Ok(games),
+            Poll::Pending => panic!("reading a byte slice should not block"),
+        }
+    }
+}
+
+#[test]
+fn same_games_as_sync_parser() {
+    let expected: Result<Vec<_>> = Parser::new(LOG.as_bytes()).collect();
+    let expected = expected.unwrap();
+    let actual = collect(AsyncParser::new(LOG.as_bytes())).unwrap();
+
+    assert_eq!(actual.len(), 2);
+    assert_eq!(actual[0].dialect, Dialect::Baseq3);
+    assert_eq!(actual[1].dialect, Dialect::UrbanTerror);
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn forced_dialect() {
+    let parser = AsyncParser::new(LOG.as_bytes()).with_dialect(Dialect::Baseq3);
+    let games = collect(parser).unwrap();
+    assert!(games.iter().all(|game| game.dialect == Dialect::Baseq3));
+}
+
+#[test]
+fn invalid_utf8() {
+    let log = b"  0:00 InitGame: \\g_gametype\\0\n  0:01 \xff\n";
+    assert!(collect(AsyncParser::new(&log[..])).is_err());
+}
@@ -1,9 +1,26 @@
 use super::{Event, Parser, RawEvent, State};
 use crate::{
     error::Result,
-    game::{Game, Kill, Killer, MeansOfDeath, PlayerName},
+    game::{
+        Award,
+        Dialect,
+        FlagAction,
+        FlagEvent,
+        GainedAward,
+        Game,
+        GameEvent,
+        GameEventKind,
+        GameType,
+        Kill,
+        Killer,
+        MeansOfDeath,
+        PlayerId,
+        PlayerName,
+        Team,
+        Timestamp,
+    },
 };
-use std::collections::HashMap;
+use std::{collections::HashMap, env, fs, process};
 
 const SMALL_LOG: &str = concat!(
     "  0:00 ------------------------------------------------------------\n",
@@ -68,43 +85,103 @@ const SMALL_LOG: &str = concat!(
     "  2:04 Kill: 1022 3 19: <world> killed Isgalamido by MOD_FALLING\n",
     "  2:07 Item: 2 weapon_rocketlauncher\n",
     "  2:11 Kill: 2 4 6: Dono da Bola killed Zeh by MOD_ROCKET\n",
+    "  2:11 Award: 2 1: Dono da Bola gained the IMPRESSIVE award!\n",
     " 12:13 ShutdownGame:\n",
 );
 
+fn event(kind: GameEventKind, time: Timestamp) -> GameEvent {
+    GameEvent { kind, time }
+}
+
+fn info_changed(player: PlayerId, name: &str, time: Timestamp) -> GameEvent {
+    let name = PlayerName::from(name);
+    let team = Some(Team::Free);
+    event(GameEventKind::PlayerInfoChanged { player, name, team }, time)
+}
+
 fn expected_games_from_small_log() -> Vec<Game> {
     vec![
         Game {
+            game_type: GameType::FreeForAll,
+            dialect: Dialect::Baseq3,
+            version: Some(String::from("ioq3 1.36 linux-x86_64 Apr 12 2009")),
+            map: Some(String::from("q3dm17")),
+            start_time: 0,
+            end_time: Some(20 * 60 + 37),
             players: HashMap::from([(2, PlayerName::from("Isgalamido"))]),
+            teams: HashMap::from([(2, Team::Free)]),
             kills: Vec::new(),
+            awards: HashMap::new(),
+            flag_events: Vec::new(),
+            events: vec![
+                event(GameEventKind::Connect { player: 2 }, 20 * 60 + 34),
+                info_changed(2, "Isgalamido", 20 * 60 + 34),
+                info_changed(2, "Isgalamido", 20 * 60 + 37),
+            ],
         },
         Game {
+            game_type: GameType::FreeForAll,
+            dialect: Dialect::Baseq3,
+            version: Some(String::from("ioq3 1.36 linux-x86_64 Apr 12 2009")),
+            map: Some(String::from("q3dm17")),
+            start_time: 60 + 47,
+            end_time: Some(12 * 60 + 13),
             players: HashMap::from([
                 (2, PlayerName::from("Dono da Bola")),
                 (3, PlayerName::from("Isgalamido")),
                 (4, PlayerName::from("Zeh")),
             ]),
+            teams: HashMap::from([
+                (2, Team::Free),
+                (3, Team::Free),
+                (4, Team::Free),
+            ]),
             kills: vec![
                 Kill {
                     killer: Killer::World,
                     target: 3,
                     means: MeansOfDeath::from("MOD_TRIGGER_HURT"),
+                    time: 2 * 60,
                 },
                 Kill {
                     killer: Killer::World,
                     target: 2,
                     means: MeansOfDeath::from("MOD_FALLING"),
+                    time: 2 * 60 + 4,
                 },
                 Kill {
                     killer: Killer::World,
                     target: 3,
                     means: MeansOfDeath::from("MOD_FALLING"),
+                    time: 2 * 60 + 4,
                 },
                 Kill {
                     killer: Killer::Player(2),
                     target: 4,
                     means: MeansOfDeath::from("MOD_ROCKET"),
+                    time: 2 * 60 + 11,
                 },
             ],
+            awards: HashMap::from([(
+                2,
+                vec![GainedAward {
+                    award: Award::Impressive,
+                    time: 2 * 60 + 11,
+                }],
+            )]),
+            flag_events: Vec::new(),
+            events: vec![
+                event(GameEventKind::Connect { player: 2 }, 60 + 47),
+                info_changed(2, "Dono da Bola", 60 + 47),
+                event(GameEventKind::Connect { player: 3 }, 60 + 47),
+                info_changed(3, "Isgalamido", 60 + 47),
+                event(GameEventKind::Connect { player: 4 }, 60 + 47),
+                info_changed(4, "Zeh", 60 + 47),
+                event(GameEventKind::Kill { index: 0 }, 2 * 60),
+                event(GameEventKind::Kill { index: 1 }, 2 * 60 + 4),
+                event(GameEventKind::Kill { index: 2 }, 2 * 60 + 4),
+                event(GameEventKind::Kill { index: 3 }, 2 * 60 + 11),
+            ],
         },
     ]
 }
@@ -112,7 +189,11 @@ fn expected_games_from_small_log() -> Vec<Game> {
 #[test]
 fn split_raw_event_easy_case() {
     let line = " 20:34 ClientConnect: 2\n";
-    let expected = Some(RawEvent { key: "ClientConnect", raw_data: "2" });
+    let expected = Some(RawEvent {
+        time: Some(20 * 60 + 34),
+        key: "ClientConnect",
+        raw_data: "2",
+    });
     let actual = RawEvent::from_line(line);
     assert_eq!(expected, actual);
 }
@@ -120,7 +201,11 @@ fn split_raw_event_easy_case() {
 #[test]
 fn split_raw_event_single_digit_hour() {
     let line = "  1:48 Item: 4 ammo_rockets\n";
-    let expected = Some(RawEvent { key: "Item", raw_data: "4 ammo_rockets" });
+    let expected = Some(RawEvent {
+        time: Some(60 + 48),
+        key: "Item",
+        raw_data: "4 ammo_rockets",
+    });
     let actual = RawEvent::from_line(line);
     assert_eq!(expected, actual);
 }
@@ -143,6 +228,27 @@ fn split_raw_event_corrupted_line() {
     assert_eq!(expected, actual);
 }
 
+#[test]
+fn split_raw_event_garbage_before_time() {
+    let line = " 26  0:00 InitGame: \\g_gametype\\0\n";
+    let expected = Some(RawEvent {
+        time: Some(0),
+        key: "InitGame",
+        raw_data: "\\g_gametype\\0",
+    });
+    let actual = RawEvent::from_line(line);
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn split_raw_event_without_time() {
+    let line = "ShutdownGame:\n";
+    let expected =
+        Some(RawEvent { time: None, key: "ShutdownGame", raw_data: "" });
+    let actual = RawEvent::from_line(line);
+    assert_eq!(expected, actual);
+}
+
 #[test]
 fn parse_init_game() {
     let line = "  0:00 InitGame: \
@@ -157,16 +263,37 @@ fn parse_init_game() {
                 version\\\\ioq3 1.36 linux-x86_64 Apr 12 \
                 2009\\\\protocol\\\\68\\\\mapname\\\\q3dm17\\\\gamename\\\\\
                 baseq3\\\\g_needpass\\\\0\n";
-    let expected = Some(Event::Init);
-    let actual = RawEvent::from_line(line).unwrap().parse();
+    let expected = Some(Event::Init {
+        game_type_code: None,
+        game_name: None,
+        version: None,
+        map: None,
+        time: Some(0),
+    });
+    let actual = RawEvent::from_line(line).unwrap().parse(Dialect::Baseq3);
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn parse_init_team_game() {
+    let line = "  0:00 InitGame: \\sv_floodProtect\\1\\g_gametype\\3\\\
+                mapname\\q3dm17\\gamename\\baseq3\n";
+    let expected = Some(Event::Init {
+        game_type_code: Some(3),
+        game_name: Some(String::from("baseq3")),
+        version: None,
+        map: Some(String::from("q3dm17")),
+        time: Some(0),
+    });
+    let actual = RawEvent::from_line(line).unwrap().parse(Dialect::Baseq3);
     assert_eq!(expected, actual);
 }
 
 #[test]
 fn parse_shutdown_game() {
     let line = " 54:21 ShutdownGame:\n";
-    let expected = Some(Event::Shutdown);
-    let actual = RawEvent::from_line(line).unwrap().parse();
+    let expected = Some(Event::Shutdown { time: Some(54 * 60 + 21) });
+    let actual = RawEvent::from_line(line).unwrap().parse(Dialect::Baseq3);
     assert_eq!(expected, actual);
 }
 
@@ -177,11 +304,42 @@ fn parse_player_name_changed() {
                 g_blueteam\\\\c1\\3\\c2\\5\\hc\\100\\w\\0\\l\\0\\tt\\0\\tl\\0\\
                 \
                 \n";
-    let expected = Some(Event::PlayerNameChanged {
+    let expected = Some(Event::PlayerInfoChanged {
         id: 2,
         name: PlayerName::from("Fasano Again"),
+        team: Some(Team::Free),
+        time: Some(7),
+    });
+    let actual = RawEvent::from_line(line).unwrap().parse(Dialect::Baseq3);
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn parse_player_team_changed() {
+    let line = "  0:07 ClientUserinfoChanged: 3 n\\Zeh\\t\\2\\model\\sarge\n";
+    let expected = Some(Event::PlayerInfoChanged {
+        id: 3,
+        name: PlayerName::from("Zeh"),
+        team: Some(Team::Blue),
+        time: Some(7),
     });
-    let actual = RawEvent::from_line(line).unwrap().parse();
+    let actual = RawEvent::from_line(line).unwrap().parse(Dialect::Baseq3);
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn parse_client_connect() {
+    let line = " 20:34 ClientConnect: 2\n";
+    let expected = Some(Event::Connect { id: 2, time: Some(20 * 60 + 34) });
+    let actual = RawEvent::from_line(line).unwrap().parse(Dialect::Baseq3);
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn parse_client_disconnect() {
+    let line = "  3:21 ClientDisconnect: 4\n";
+    let expected = Some(Event::Disconnect { id: 4, time: Some(3 * 60 + 21) });
+    let actual = RawEvent::from_line(line).unwrap().parse(Dialect::Baseq3);
     assert_eq!(expected, actual);
 }
 
@@ -192,8 +350,9 @@ fn parse_kill_by_player() {
         killer: Killer::Player(2),
         target: 4,
         means: MeansOfDeath::from("MOD_ROCKET"),
+        time: Some(25),
     });
-    let actual = RawEvent::from_line(line).unwrap().parse();
+    let actual = RawEvent::from_line(line).unwrap().parse(Dialect::Baseq3);
     assert_eq!(expected, actual);
 }
 
@@ -205,8 +364,71 @@ fn parse_kill_by_world() {
         killer: Killer::World,
         target: 5,
         means: MeansOfDeath::from("MOD_TRIGGER_HURT"),
+        time: Some(15 * 60 + 27),
+    });
+    let actual = RawEvent::from_line(line).unwrap().parse(Dialect::Baseq3);
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn parse_kill_unknown_means() {
+    let line = "  0:25 Kill: 2 4 40: Oootsimo killed Zeh by MOD_SWORD\n";
+    let expected = Some(Event::Kill {
+        killer: Killer::Player(2),
+        target: 4,
+        means: MeansOfDeath::Other(String::from("MOD_SWORD")),
+        time: Some(25),
+    });
+    let actual = RawEvent::from_line(line).unwrap().parse(Dialect::Baseq3);
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn parse_kill_means_code_without_name() {
+    let line = "  0:25 Kill: 2 4 10: Oootsimo killed Zeh\n";
+    let expected = Some(Event::Kill {
+        killer: Killer::Player(2),
+        target: 4,
+        means: MeansOfDeath::Railgun,
+        time: Some(25),
+    });
+    let actual = RawEvent::from_line(line).unwrap().parse(Dialect::Baseq3);
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn parse_award() {
+    let line = "  3:07 Award: 3 2: Zeh gained the IMPRESSIVE award!\n";
+    let expected = Some(Event::Award {
+        id: 3,
+        award: Award::Impressive,
+        time: Some(3 * 60 + 7),
+    });
+    let actual = RawEvent::from_line(line).unwrap().parse(Dialect::Baseq3);
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn parse_award_player_name_with_gained() {
+    let line = "  3:07 Award: 3 4: I gained the Defence award!\n";
+    let expected = Some(Event::Award {
+        id: 3,
+        award: Award::Defend,
+        time: Some(3 * 60 + 7),
     });
-    let actual = RawEvent::from_line(line).unwrap().parse();
+    let actual = RawEvent::from_line(line).unwrap().parse(Dialect::Baseq3);
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn parse_flag_item() {
+    let line = "  4:02 Item: 5 team_CTF_blueflag\n";
+    let expected = Some(Event::FlagTouch {
+        id: 5,
+        flag: Team::Blue,
+        time: Some(4 * 60 + 2),
+    });
+    let actual = RawEvent::from_line(line).unwrap().parse(Dialect::Baseq3);
     assert_eq!(expected, actual);
 }
 
@@ -214,7 +436,7 @@ fn parse_kill_by_world() {
 fn parse_irrelevant() {
     let line = " 15:43 Item: 2 weapon_shotgun\n";
     let expected = None;
-    let actual = RawEvent::from_line(line).unwrap().parse();
+    let actual = RawEvent::from_line(line).unwrap().parse(Dialect::Baseq3);
     assert_eq!(expected, actual);
 }
 
@@ -225,7 +447,7 @@ fn state_parse_games() {
     let mut state = State::default();
     let mut actual_games = Vec::new();
     for line in SMALL_LOG.lines() {
-        if let Some(game) = state.process_line(line) {
+        if let Some(game) = state.process_line(line, None) {
             actual_games.push(game);
         }
     }
@@ -247,3 +469,313 @@ fn actual_parse_games() {
 
     assert_eq!(expected, actual);
 }
+
+#[test]
+fn parse_games_split_across_files() {
+    let expected = expected_games_from_small_log();
+
+    let dir = env::temp_dir()
+        .join(format!("quake-log-parser-rotated-{}", process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let paths = [dir.join("qgames.log.1"), dir.join("qgames.log")];
+    let (older, newer) = SMALL_LOG.split_at(SMALL_LOG.len() / 2);
+    fs::write(&paths[0], older).unwrap();
+    fs::write(&paths[1], newer).unwrap();
+
+    let result: Result<Vec<_>> = Parser::from_paths(&paths).collect();
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(expected, result.unwrap());
+}
+
+#[test]
+fn state_flag_events() {
+    let log = concat!(
+        "  0:00 InitGame: \\g_gametype\\4\\mapname\\q3ctf1\n",
+        "  0:01 ClientUserinfoChanged: 2 n\\Zeh\\t\\1\n",
+        "  0:01 ClientUserinfoChanged: 3 n\\Mal\\t\\1\n",
+        "  0:01 ClientUserinfoChanged: 4 n\\Isgalamido\\t\\2\n",
+        "  0:10 Item: 2 team_CTF_blueflag\n",
+        "  0:15 Kill: 4 2 10: Isgalamido killed Zeh by MOD_RAILGUN\n",
+        "  0:17 Item: 4 team_CTF_blueflag\n",
+        "  0:20 Item: 3 team_CTF_blueflag\n",
+        "  0:30 Item: 3 team_CTF_redflag\n",
+        "  0:31 Item: 3 team_CTF_redflag\n",
+        "  0:40 ShutdownGame:\n",
+    );
+    let expected = vec![
+        FlagEvent {
+            flag: Team::Blue,
+            action: FlagAction::Pickup,
+            player: 2,
+            time: 10,
+        },
+        FlagEvent {
+            flag: Team::Blue,
+            action: FlagAction::Drop { killer: Some(Killer::Player(4)) },
+            player: 2,
+            time: 15,
+        },
+        FlagEvent {
+            flag: Team::Blue,
+            action: FlagAction::Return,
+            player: 4,
+            time: 17,
+        },
+        FlagEvent {
+            flag: Team::Blue,
+            action: FlagAction::Pickup,
+            player: 3,
+            time: 20,
+        },
+        FlagEvent {
+            flag: Team::Blue,
+            action: FlagAction::Capture,
+            player: 3,
+            time: 30,
+        },
+        FlagEvent {
+            flag: Team::Red,
+            action: FlagAction::Return,
+            player: 3,
+            time: 31,
+        },
+    ];
+
+    let mut state = State::default();
+    let games: Vec<_> = log
+        .lines()
+        .filter_map(|line| state.process_line(line, None))
+        .collect();
+
+    assert_eq!(games.len(), 1);
+    assert_eq!(games[0].game_type, GameType::CaptureTheFlag);
+    assert_eq!(expected, games[0].flag_events);
+}
+
+#[test]
+fn state_carrier_returns_own_dropped_flag() {
+    let log = concat!(
+        "  0:00 InitGame: \\g_gametype\\4\\mapname\\q3ctf1\n",
+        "  0:01 ClientUserinfoChanged: 2 n\\Zeh\\t\\1\n",
+        "  0:01 ClientUserinfoChanged: 4 n\\Isgalamido\\t\\2\n",
+        "  0:10 Item: 2 team_CTF_blueflag\n",
+        "  0:12 Item: 4 team_CTF_redflag\n",
+        "  0:15 Kill: 2 4 10: Zeh killed Isgalamido by MOD_RAILGUN\n",
+        "  0:17 Item: 2 team_CTF_redflag\n",
+        "  0:40 ShutdownGame:\n",
+    );
+    let mut state = State::default();
+    let games: Vec<_> = log
+        .lines()
+        .filter_map(|line| state.process_line(line, None))
+        .collect();
+
+    let game = &games[0];
+    let expected = FlagEvent {
+        flag: Team::Red,
+        action: FlagAction::Return,
+        player: 2,
+        time: 17,
+    };
+    assert_eq!(game.flag_events.last(), Some(&expected));
+    let actions: Vec<_> =
+        game.flag_events.iter().map(|event| event.action).collect();
+    assert_eq!(
+        actions.iter().filter(|&&action| action == FlagAction::Return).count(),
+        1,
+    );
+    assert!(!actions.contains(&FlagAction::Capture));
+    assert_eq!(game.flag_carrier(Team::Blue), Some(2));
+}
+
+fn parse_log(log: &str, dialect: Dialect) -> Vec<Game> {
+    let result: Result<Vec<_>> =
+        Parser::new(log.as_bytes()).with_dialect(dialect).collect();
+    result.unwrap()
+}
+
+const MISSIONPACK_LOG: &str = concat!(
+    "  0:00 InitGame: \\g_gametype\\0\\gamename\\missionpack\n",
+    "  0:01 ClientUserinfoChanged: 2 n\\Zeh\\t\\0\n",
+    "  0:01 ClientUserinfoChanged: 3 n\\Mal\\t\\0\n",
+    "  0:05 Kill: 2 3 23: Zeh killed Mal by MOD_NAIL\n",
+    "  0:09 Kill: 3 2 28: Mal killed Zeh\n",
+    "  0:20 ShutdownGame:\n",
+);
+
+#[test]
+fn missionpack_sample_log() {
+    let games = parse_log(MISSIONPACK_LOG, Dialect::MissionPack);
+    assert_eq!(games.len(), 1);
+    assert_eq!(games[0].dialect, Dialect::MissionPack);
+    let means: Vec<_> = games[0].kills.iter().map(|kill| &kill.means).collect();
+    assert_eq!(means, [&MeansOfDeath::Nail, &MeansOfDeath::Grapple]);
+
+    let games = parse_log(MISSIONPACK_LOG, Dialect::Baseq3);
+    assert_eq!(games[0].kills.len(), 1);
+}
+
+const OPEN_ARENA_LOG: &str = concat!(
+    "  0:00 InitGame: \\g_gametype\\4\\gamename\\baseoa\n",
+    "  0:01 ClientUserinfoChanged: 2 n\\Zeh\\t\\1\n",
+    "  0:01 ClientUserinfoChanged: 4 n\\Isgalamido\\t\\2\n",
+    "  0:10 Item: 2 team_CTF_blueflag\n",
+    "  0:10 CTF: 2 2 0: Zeh got the BLUE flag!\n",
+    "  0:20 Item: 2 team_CTF_redflag\n",
+    "  0:20 CTF: 2 2 1: Zeh captured the BLUE flag!\n",
+    "  0:25 Item: 4 team_CTF_redflag\n",
+    "  0:25 CTF: 4 1 0: Isgalamido got the RED flag!\n",
+    "  0:30 Kill: 2 4 10: Zeh killed Isgalamido by MOD_RAILGUN\n",
+    "  0:30 CTF: 2 1 3: Zeh fragged RED's flag carrier!\n",
+    "  0:33 CTF: 2 1 2: Zeh returned the RED flag!\n",
+    "  0:40 ShutdownGame:\n",
+);
+
+#[test]
+fn open_arena_sample_log() {
+    let games = parse_log(OPEN_ARENA_LOG, Dialect::OpenArena);
+    assert_eq!(games.len(), 1);
+    assert_eq!(games[0].game_type, GameType::CaptureTheFlag);
+    let expected = vec![
+        FlagEvent {
+            flag: Team::Blue,
+            action: FlagAction::Pickup,
+            player: 2,
+            time: 10,
+        },
+        FlagEvent {
+            flag: Team::Blue,
+            action: FlagAction::Capture,
+            player: 2,
+            time: 20,
+        },
+        FlagEvent {
+            flag: Team::Red,
+            action: FlagAction::Pickup,
+            player: 4,
+            time: 25,
+        },
+        FlagEvent {
+            flag: Team::Red,
+            action: FlagAction::Drop { killer: Some(Killer::Player(2)) },
+            player: 4,
+            time: 30,
+        },
+        FlagEvent {
+            flag: Team::Red,
+            action: FlagAction::Return,
+            player: 2,
+            time: 33,
+        },
+    ];
+    assert_eq!(expected, games[0].flag_events);
+}
+
+const URBAN_TERROR_LOG: &str = concat!(
+    "  0:00 InitGame: \\g_gametype\\7\\gamename\\q3urt42\\mapname\\\
+     ut4_turnpike\n",
+    "  0:01 ClientConnect: 0\n",
+    "  0:01 ClientUserinfoChanged: 0 n\\Zeh\\t\\1\\r\\1\\tl\\0\n",
+    "  0:01 ClientConnect: 1\n",
+    "  0:01 ClientUserinfoChanged: 1 n\\Mal\\t\\2\\r\\0\\tl\\0\n",
+    "  0:10 Item: 0 team_CTF_blueflag\n",
+    "  0:15 Flag: 0 2: team_CTF_blueflag\n",
+    "  0:15 Flag Return: BLUE\n",
+    "  0:20 Item: 1 team_CTF_redflag\n",
+    "  0:22 Kill: 0 1 19: Zeh killed Mal by UT_MOD_LR300\n",
+    "  0:22 Flag: 1 0: team_CTF_redflag\n",
+    "  0:25 Item: 0 team_CTF_redflag\n",
+    "  0:25 Flag: 0 1: team_CTF_redflag\n",
+    "  0:30 ShutdownGame:\n",
+);
+
+#[test]
+fn urban_terror_sample_log() {
+    let games = parse_log(URBAN_TERROR_LOG, Dialect::UrbanTerror);
+    assert_eq!(games.len(), 1);
+    let game = &games[0];
+    assert_eq!(game.game_type, GameType::CaptureTheFlag);
+    let expected = vec![Kill {
+        killer: Killer::Player(0),
+        target: 1,
+        means: MeansOfDeath::from("UT_MOD_LR300"),
+        time: 22,
+    }];
+    assert_eq!(expected, game.kills);
+    let expected = vec![
+        FlagEvent {
+            flag: Team::Blue,
+            action: FlagAction::Pickup,
+            player: 0,
+            time: 10,
+        },
+        FlagEvent {
+            flag: Team::Blue,
+            action: FlagAction::Capture,
+            player: 0,
+            time: 15,
+        },
+        FlagEvent {
+            flag: Team::Red,
+            action: FlagAction::Pickup,
+            player: 1,
+            time: 20,
+        },
+        FlagEvent {
+            flag: Team::Red,
+            action: FlagAction::Drop { killer: Some(Killer::Player(0)) },
+            player: 1,
+            time: 22,
+        },
+        FlagEvent {
+            flag: Team::Red,
+            action: FlagAction::Return,
+            player: 0,
+            time: 25,
+        },
+    ];
+    assert_eq!(expected, game.flag_events);
+}
+
+#[test]
+fn urban_terror_drop_without_kill() {
+    let log = concat!(
+        "  0:00 InitGame: \\g_gametype\\7\\gamename\\q3urt42\n",
+        "  0:01 ClientConnect: 0\n",
+        "  0:01 ClientUserinfoChanged: 0 n\\Zeh\\t\\1\\r\\1\\tl\\0\n",
+        "  0:10 Item: 0 team_CTF_blueflag\n",
+        "  0:12 Flag: 0 0: team_CTF_blueflag\n",
+        "  0:30 ShutdownGame:\n",
+    );
+    let games = parse_log(log, Dialect::UrbanTerror);
+    let expected = FlagEvent {
+        flag: Team::Blue,
+        action: FlagAction::Drop { killer: None },
+        player: 0,
+        time: 12,
+    };
+    assert_eq!(Some(&expected), games[0].flag_events.last());
+}
+
+#[test]
+fn detect_dialect_of_each_game() {
+    let log = [MISSIONPACK_LOG, OPEN_ARENA_LOG, URBAN_TERROR_LOG, SMALL_LOG];
+    let result: Result<Vec<_>> = Parser::new(log.concat().as_bytes()).collect();
+    let games = result.unwrap();
+
+    let dialects: Vec<_> = games.iter().map(|game| game.dialect).collect();
+    let expected = [
+        Dialect::MissionPack,
+        Dialect::OpenArena,
+        Dialect::UrbanTerror,
+        Dialect::Baseq3,
+        Dialect::Baseq3,
+    ];
+    assert_eq!(expected, dialects.as_slice());
+    assert_eq!(games[0].kills.len(), 2);
+    assert_eq!(games[1].flag_events.len(), 5);
+    assert_eq!(games[2].game_type, GameType::CaptureTheFlag);
+    assert_eq!(games[2].flag_events.len(), 5);
+    assert_eq!(&games[3 ..], expected_games_from_small_log().as_slice());
+}
@@ -4,16 +4,53 @@
 use crate::{
     error::Result,
     game::{
-        all_means_of_death,
+        Award,
+        AwardCount,
         Game,
+        GameType,
         KillCount,
-        Killer,
         MeansOfDeath,
         PlayerName,
+        Team,
+        Timestamp,
+        Weapon,
+        WeaponCategory,
     },
 };
-use anyhow::anyhow;
+use ctf::CtfReport;
+use highlights::{Highlights, HighlightsConfig};
 use indexmap::{IndexMap, IndexSet};
+use matrix::KillMatrix;
+use ranking::{Ranking, RankingKey};
+use rating::{RatingSystem, Ratings};
+use stats::PlayerStats;
+use timeline::{ScoreTimeline, TimelineConfig};
+use totals::{IdentityKey, PlayerTotals};
+
+#[cfg(feature = "charts")]
+pub mod charts;
+pub mod ctf;
+pub mod highlights;
+#[cfg(feature = "html")]
+pub mod html;
+pub mod matrix;
+pub mod ranking;
+pub mod rating;
+#[cfg(any(
+    feature = "json",
+    feature = "ndjson",
+    feature = "yaml",
+    feature = "csv",
+    feature = "markdown",
+    feature = "scoreboard",
+    feature = "html",
+))]
+pub mod render;
+pub mod stats;
+#[cfg(feature = "json")]
+pub mod stream;
+pub mod timeline;
+pub mod totals;
 
 #[cfg(test)]
 mod test;
@@ -24,10 +61,54 @@ mod test;
 /// string.
 pub type GameName = String;
 
+pub use crate::game::GameCount;
+
+/// Configuration of report generation. The default configuration is used by
+/// the `generate` functions, while the `generate_with` functions take it
+/// explicitly.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReportConfig {
+    /// How players are identified across games in log-level sections.
+    pub identity: IdentityKey,
+    /// Keys by which rankings are sorted, the first one being the primary
+    /// key and the following ones breaking ties.
+    pub ranking_keys: Vec<RankingKey>,
+    /// Rating system used to rate players across games, if any.
+    pub rating: Option<RatingSystem>,
+    /// How highlights such as multi-kills are detected in each game.
+    pub highlights: HighlightsConfig,
+    /// How score timelines are sampled in each game, if they are wanted.
+    pub timeline: Option<TimelineConfig>,
+    /// How many games of the log were reported before the given ones, e.g. in
+    /// a previous run resumed from a checkpoint. Game numbering continues
+    /// after them.
+    pub games_before: GameCount,
+}
+
+impl Default for ReportConfig {
+    fn default() -> Self {
+        Self {
+            identity: IdentityKey::default(),
+            ranking_keys: Ranking::DEFAULT_KEYS.to_vec(),
+            rating: None,
+            highlights: HighlightsConfig::default(),
+            timeline: None,
+            games_before: 0,
+        }
+    }
+}
+
 /// A datatype representing the report of a single game, friendly to `serde`
 /// (serialization/deserialization library).
-#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
 pub struct GameReport {
+    /// The type of the game, such as free for all or team deathmatch.
+    pub game_type: GameType,
+    /// Name of the map the game was played in, if known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub map: Option<String>,
+    /// How long the game lasted, in seconds.
+    pub duration: Timestamp,
     /// Total kill count in the game, including the world's.
     pub total_kills: KillCount,
     /// Set of player names that were in the match, more specifically, the last
@@ -37,72 +118,189 @@ pub struct GameReport {
     /// discounting `1` for each time they died because of the "world".
     pub kills: IndexMap<PlayerName, KillCount>,
     /// The dictionary counting how many killings happened using each means of
-    /// death.
+    /// death. MODs not expected in the game's dialect follow the expected
+    /// ones.
     pub kills_by_means: IndexMap<MeansOfDeath, KillCount>,
+    /// The dictionary counting how many killings happened using each weapon,
+    /// grouping MODs such as direct and splash hits of the same weapon.
+    pub kills_by_weapon: IndexMap<Weapon, KillCount>,
+    /// The dictionary counting how many killings happened in each category
+    /// of MODs, such as hitscan or environmental.
+    pub kills_by_category: IndexMap<WeaponCategory, KillCount>,
+    /// Head-to-head kill counts between the players of the game.
+    pub kill_matrix: KillMatrix,
+    /// Players sorted by the configured ranking keys.
+    pub ranking: Ranking,
+    /// The mapping of player names to the teams they were last in.
+    pub teams: IndexMap<PlayerName, Team>,
+    /// The mapping of teams to their scores, i.e. the sum of their players'
+    /// scores. Only filled in team games.
+    pub team_scores: IndexMap<Team, KillCount>,
+    /// First blood, killing streaks, multi-kills and shutdowns.
+    pub highlights: Highlights,
+    /// The mapping of player names to how many times they gained each award.
+    pub awards: IndexMap<PlayerName, IndexMap<Award, AwardCount>>,
+    /// Flag possessions, captures, returns and carrier kills. Only filled in
+    /// capture the flag games.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ctf: Option<CtfReport>,
+    /// Scores over time, with lead changes and comebacks, if configured.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeline: Option<ScoreTimeline>,
 }
 
 impl GameReport {
     /// Generate the report object from the given game data.
     pub fn generate(game: &Game) -> Result<Self> {
+        Self::generate_with(game, &ReportConfig::default())
+    }
+
+    /// Generate the report object from the given game data and the given
+    /// configuration.
+    pub fn generate_with(game: &Game, config: &ReportConfig) -> Result<Self> {
+        let stats = PlayerStats::generate_all(game);
+        Self::generate_from_stats(game, &stats, config)
+    }
+
+    /// Generate the report object from the given game data and the
+    /// previously generated player statistics of the same game.
+    fn generate_from_stats(
+        game: &Game,
+        stats: &IndexMap<PlayerName, PlayerStats>,
+        config: &ReportConfig,
+    ) -> Result<Self> {
         let total_kills =
             KillCount::try_from(game.kills.len()).unwrap_or(KillCount::MAX);
 
         let players = game.players.values().cloned().collect();
 
-        let kills =
-            game.players.values().cloned().map(|name| (name, 0)).collect();
+        let kills = stats
+            .iter()
+            .map(|(name, player_stats)| (name.clone(), player_stats.score))
+            .collect();
 
-        let kills_by_means = all_means_of_death()
+        let mut kills_by_means: IndexMap<_, _> = game
+            .dialect
+            .known_means()
             .iter()
-            .copied()
-            .map(|means| (MeansOfDeath::from(means), 0))
+            .cloned()
+            .map(|means| (means, 0))
             .collect();
 
-        let mut this = Self { total_kills, players, kills, kills_by_means };
+        let mut kills_by_weapon: IndexMap<_, _> =
+            Weapon::ALL.iter().map(|&weapon| (weapon, 0)).collect();
+        let mut kills_by_category: IndexMap<_, _> =
+            WeaponCategory::ALL.iter().map(|&category| (category, 0)).collect();
 
         for kill in &game.kills {
-            match kill.killer {
-                Killer::World => {
-                    let player_name = &game.players[&kill.target];
-                    if let Some(kills) = this.kills.get_mut(player_name) {
-                        *kills -= 1;
-                    } else {
-                        log::error!(
-                            "Bad game report: player {:?} (kill target) was \
-                             not found",
-                            player_name
-                        );
-                    }
-                },
-                Killer::Player(killer_id) => {
-                    let player_name = &game.players[&killer_id];
-                    if let Some(kills) = this.kills.get_mut(player_name) {
-                        *kills += 1;
-                    } else {
-                        log::error!(
-                            "Bad game report: player {:?} (killer) was not \
-                             found",
-                            player_name
-                        );
-                    }
-                },
+            *kills_by_means.entry(kill.means.clone()).or_insert(0) += 1;
+            *kills_by_weapon.entry(kill.means.weapon()).or_insert(0) += 1;
+            *kills_by_category.entry(kill.means.category()).or_insert(0) += 1;
+        }
+
+        let kill_matrix = KillMatrix::generate(game);
+
+        let ranking = Ranking::generate(stats, &config.ranking_keys);
+
+        let teams: IndexMap<_, _> = game
+            .players
+            .iter()
+            .filter_map(|(id, name)| {
+                let team = game.teams.get(id)?;
+                Some((name.clone(), *team))
+            })
+            .collect();
+
+        let mut team_scores = IndexMap::new();
+        if game.game_type.is_team_game() {
+            team_scores.insert(Team::Red, 0);
+            team_scores.insert(Team::Blue, 0);
+            for (name, team) in &teams {
+                let player_stats = stats.get(name);
+                if let (Some(score), Some(player_stats)) =
+                    (team_scores.get_mut(team), player_stats)
+                {
+                    *score += player_stats.score;
+                }
+            }
+        }
+
+        let highlights = Highlights::generate(game, &config.highlights);
+
+        let mut awards: IndexMap<_, IndexMap<_, _>> = game
+            .players
+            .values()
+            .map(|name| {
+                let counts = Award::ALL.iter().map(|&award| (award, 0));
+                (name.clone(), counts.collect())
+            })
+            .collect();
+        for (id, gained_awards) in &game.awards {
+            let Some(counts) =
+                game.players.get(id).and_then(|name| awards.get_mut(name))
+            else {
+                log::warn!("Awards of unnamed player {} ignored", id);
+                continue;
+            };
+            for gained_award in gained_awards {
+                *counts.entry(gained_award.award).or_insert(0) += 1;
             }
-            let kills =
-                this.kills_by_means.get_mut(&kill.means).ok_or_else(|| {
-                    anyhow!("unknown means of killing: {}", kill.means)
-                })?;
-            *kills += 1;
         }
 
-        Ok(this)
+        let ctf = (game.game_type == GameType::CaptureTheFlag)
+            .then(|| CtfReport::generate(game));
+
+        let timeline = config
+            .timeline
+            .map(|timeline| ScoreTimeline::generate(game, &timeline));
+
+        Ok(Self {
+            game_type: game.game_type,
+            map: game.map.clone(),
+            duration: game.duration(),
+            total_kills,
+            players,
+            kills,
+            kills_by_means,
+            kills_by_weapon,
+            kills_by_category,
+            kill_matrix,
+            ranking,
+            teams,
+            team_scores,
+            highlights,
+            awards,
+            ctf,
+            timeline,
+        })
     }
 }
 
 /// A report of the full Quake III: Arena log file.
-#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
 pub struct LogReport {
     /// Dictionary of game reports.
     pub games: IndexMap<GameName, GameReport>,
+    /// Sections aggregated across all games, serialized as fields of the
+    /// report itself.
+    #[serde(flatten)]
+    pub aggregates: LogAggregates,
+}
+
+/// Sections of the log report aggregated across all games.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct LogAggregates {
+    /// Head-to-head kill counts between players, aggregated across all games.
+    pub kill_matrix: KillMatrix,
+    /// Statistics of each player, aggregated across all games.
+    pub player_totals: PlayerTotals,
+    /// Players sorted by the configured ranking keys, using their statistics
+    /// aggregated across all games.
+    pub ranking: Ranking,
+    /// Skill ratings of each player, updated game by game, if a rating system
+    /// was configured.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ratings: Option<Ratings>,
 }
 
 impl LogReport {
@@ -112,13 +310,91 @@ impl LogReport {
     where
         I: IntoIterator<Item = Result<Game>>,
     {
-        let mut this = Self { games: IndexMap::new() };
-        for (i, result) in game_iter.into_iter().enumerate() {
-            let game = result?;
-            let game_report = GameReport::generate(&game)?;
-            let game_id = format!("game_{}", i + 1);
-            this.games.insert(game_id, game_report);
+        Self::generate_with(game_iter, &ReportConfig::default())
+    }
+
+    /// Generates a report of the whole log file using an iterator over game
+    /// data and the given configuration.
+    pub fn generate_with<I>(game_iter: I, config: &ReportConfig) -> Result<Self>
+    where
+        I: IntoIterator<Item = Result<Game>>,
+    {
+        let mut builder = LogReportBuilder::new(config);
+        for result in game_iter {
+            builder.add_game(&result?)?;
+        }
+        Ok(builder.finish())
+    }
+}
+
+/// Builder of a report of the whole log file, fed game by game. Sections
+/// aggregated across games are updated incrementally, so game reports need
+/// not be kept in the report, e.g. when they are written as soon as they are
+/// generated.
+#[derive(Debug, Clone)]
+pub struct LogReportBuilder<'config> {
+    /// Configuration of report generation.
+    config: &'config ReportConfig,
+    /// How many games were reported so far, including the ones before the
+    /// given ones, as configured.
+    games: GameCount,
+    /// The report being built.
+    report: LogReport,
+}
+
+impl<'config> LogReportBuilder<'config> {
+    /// Creates the builder with no games, using the given configuration.
+    pub fn new(config: &'config ReportConfig) -> Self {
+        Self {
+            config,
+            games: config.games_before,
+            report: LogReport {
+                games: IndexMap::new(),
+                aggregates: LogAggregates {
+                    kill_matrix: KillMatrix::new(),
+                    player_totals: PlayerTotals::new(config.identity.clone()),
+                    ranking: Ranking::default(),
+                    ratings: config.rating.map(|system| {
+                        Ratings::new(system, config.identity.clone())
+                    }),
+                },
+            },
         }
-        Ok(this)
+    }
+
+    /// Reports the next game of the log, updating the aggregated sections.
+    /// The game name and report are returned, but not kept in the report.
+    pub fn report_game(
+        &mut self,
+        game: &Game,
+    ) -> Result<(GameName, GameReport)> {
+        let stats = PlayerStats::generate_all(game);
+        let game_report =
+            GameReport::generate_from_stats(game, &stats, self.config)?;
+        self.games += 1;
+        let game_id = format!("game_{}", self.games);
+        let aggregates = &mut self.report.aggregates;
+        aggregates.kill_matrix.merge(&game_report.kill_matrix);
+        aggregates.player_totals.add_game(&game_id, &stats);
+        if let Some(ratings) = &mut aggregates.ratings {
+            ratings.add_game(&game_id, &game_report);
+        }
+        Ok((game_id, game_report))
+    }
+
+    /// Reports the next game of the log, keeping its report in the report.
+    pub fn add_game(&mut self, game: &Game) -> Result<()> {
+        let (game_id, game_report) = self.report_game(game)?;
+        self.report.games.insert(game_id, game_report);
+        Ok(())
+    }
+
+    /// Finishes the report, ranking players by their aggregated statistics.
+    pub fn finish(mut self) -> LogReport {
+        let aggregates = &mut self.report.aggregates;
+        let total_stats = aggregates.player_totals.stats();
+        aggregates.ranking =
+            Ranking::generate(&total_stats, &self.config.ranking_keys);
+        self.report
     }
 }
@@ -0,0 +1,415 @@
+//! This module exposes charts of report data as standalone SVG images, with
+//! no browser or network needed to draw them: a line chart of scores over
+//! time, a bar chart of kills by weapon and a heatmap of the kill matrix.
+
+use super::{matrix::KillMatrix, timeline::ScoreTimeline, GameReport};
+use crate::{
+    error::Result,
+    game::{format_timestamp, strip_color_codes, KillCount, Team, Weapon},
+};
+use anyhow::bail;
+use indexmap::IndexMap;
+use std::io::Write;
+
+#[cfg(test)]
+mod test;
+
+/// Font of chart texts.
+const FONT: &str = "font-family=\"sans-serif\" font-size=\"12\"";
+
+/// Colors of the lines of a timeline, cycled through in player order.
+const PALETTE: [&str; 8] = [
+    "#1f77b4", "#ff7f0e", "#2ca02c", "#d62728", "#9467bd", "#8c564b",
+    "#e377c2", "#7f7f7f",
+];
+
+/// Color of the bars of a weapon chart and of the hottest heatmap cells.
+const BAR_COLOR: &str = "#b03a2e";
+
+/// Width of a timeline chart, in pixels.
+const TIMELINE_WIDTH: i64 = 640;
+
+/// Height of a timeline chart, in pixels.
+const TIMELINE_HEIGHT: i64 = 360;
+
+/// Space around the plot of a timeline chart, for axis labels, in pixels.
+const TIMELINE_MARGIN: i64 = 50;
+
+/// Width of the legend on the right of a timeline chart, in pixels.
+const LEGEND_WIDTH: i64 = 150;
+
+/// Width of a weapon chart, in pixels.
+const WEAPON_WIDTH: usize = 480;
+
+/// Width of the weapon labels on the left of a weapon chart, in pixels.
+const WEAPON_LABEL_WIDTH: usize = 140;
+
+/// Height of a bar of a weapon chart, in pixels, including the gap between
+/// bars.
+const WEAPON_BAR_HEIGHT: usize = 22;
+
+/// Size of a heatmap cell, in pixels.
+const CELL_SIZE: usize = 32;
+
+/// Space for player names on the left and top of a heatmap, in pixels.
+const HEATMAP_LABEL_SIZE: usize = 120;
+
+/// A chart of a game report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Chart {
+    /// Scores over time, a line per player, or per team in team games.
+    /// Requires the timeline of the game.
+    ScoreTimeline,
+    /// Kills by weapon, a bar per weapon with kills.
+    WeaponUsage,
+    /// Kills of each player (rows) on each player (columns).
+    KillMatrix,
+}
+
+impl Chart {
+    /// All charts of a game report.
+    pub const ALL: &'static [Self] =
+        &[Self::ScoreTimeline, Self::WeaponUsage, Self::KillMatrix];
+
+    /// Name of the chart, e.g. to tell apart files of the same game.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::ScoreTimeline => "timeline",
+            Self::WeaponUsage => "weapons",
+            Self::KillMatrix => "kill-matrix",
+        }
+    }
+
+    /// Draws this chart of the given game report into the given writer as an
+    /// SVG image. Fails if the chart needs data the report was generated
+    /// without, such as the timeline.
+    pub fn render<W>(self, report: &GameReport, writer: W) -> Result<()>
+    where
+        W: Write,
+    {
+        match self {
+            Self::ScoreTimeline => {
+                let Some(timeline) = &report.timeline else {
+                    bail!("The score timeline was not generated");
+                };
+                write_score_timeline(timeline, writer)
+            },
+            Self::WeaponUsage => {
+                write_weapon_usage(&report.kills_by_weapon, writer)
+            },
+            Self::KillMatrix => write_kill_heatmap(&report.kill_matrix, writer),
+        }
+    }
+}
+
+/// Escapes text to be inserted into SVG or HTML, either as content or as an
+/// attribute value.
+pub(super) fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for character in text.chars() {
+        match character {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(character),
+        }
+    }
+    escaped
+}
+
+/// Name of a player as labeled in charts, without color codes.
+fn label(name: &str) -> String {
+    escape_xml(&strip_color_codes(name))
+}
+
+/// Color of a team in charts.
+fn team_color(team: Team) -> &'static str {
+    match team {
+        Team::Red => PALETTE[3],
+        Team::Blue => PALETTE[0],
+        Team::Free | Team::Spectator => PALETTE[7],
+    }
+}
+
+/// Writes the opening tag of an SVG image with the given size and
+/// accessible description.
+fn write_svg_start<W>(
+    writer: &mut W,
+    width: impl std::fmt::Display,
+    height: impl std::fmt::Display,
+    description: &str,
+) -> Result<()>
+where
+    W: Write,
+{
+    writeln!(
+        writer,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" \
+         height=\"{}\" role=\"img\" aria-label=\"{}\" {}>",
+        width, height, description, FONT,
+    )?;
+    Ok(())
+}
+
+/// Draws a line chart of the given score timeline as an SVG image. Lines
+/// follow team scores in team games and player scores otherwise, stepping at
+/// each sample.
+pub fn write_score_timeline<W>(
+    timeline: &ScoreTimeline,
+    mut writer: W,
+) -> Result<()>
+where
+    W: Write,
+{
+    let team_game = timeline
+        .points
+        .first()
+        .is_some_and(|point| !point.team_scores.is_empty());
+    // Series are keyed by raw names, since different players may have the
+    // same name without color codes.
+    let mut series = IndexMap::<&str, (&str, Vec<_>)>::new();
+    for (i, point) in timeline.points.iter().enumerate() {
+        if team_game {
+            for (&team, &score) in &point.team_scores {
+                let (_, values) = series
+                    .entry(team.name())
+                    .or_insert_with(|| (team_color(team), Vec::new()));
+                values.push((i, score));
+            }
+        } else {
+            for (name, &score) in &point.scores {
+                let color = PALETTE[series.len() % PALETTE.len()];
+                let (_, values) = series
+                    .entry(name)
+                    .or_insert_with(|| (color, Vec::new()));
+                values.push((i, score));
+            }
+        }
+    }
+
+    let start = timeline.points.first().map_or(0, |point| point.time);
+    let end = timeline.points.last().map_or(0, |point| point.time);
+    let span = (end - start).max(1) as i64;
+    let all_scores = || series.values().flat_map(|(_, values)| values);
+    let min_score = all_scores().map(|&(_, score)| score).min().unwrap_or(0);
+    let max_score = all_scores().map(|&(_, score)| score).max().unwrap_or(0);
+    let (min_score, max_score) = (min_score.min(0), max_score.max(1));
+
+    let plot_left = TIMELINE_MARGIN;
+    let plot_right = TIMELINE_WIDTH - LEGEND_WIDTH;
+    let plot_top = TIMELINE_MARGIN / 2;
+    let plot_bottom = TIMELINE_HEIGHT - TIMELINE_MARGIN;
+    let x = |time: u64| {
+        plot_left + (time - start) as i64 * (plot_right - plot_left) / span
+    };
+    let y = |score: KillCount| {
+        plot_bottom
+            - (score - min_score) * (plot_bottom - plot_top)
+                / (max_score - min_score)
+    };
+
+    write_svg_start(
+        &mut writer,
+        TIMELINE_WIDTH,
+        TIMELINE_HEIGHT,
+        "Scores over time",
+    )?;
+    writeln!(
+        writer,
+        "<rect width=\"100%\" height=\"100%\" fill=\"white\"/>\n\
+         <line x1=\"{left}\" y1=\"{zero}\" x2=\"{right}\" y2=\"{zero}\" \
+         stroke=\"#999\"/>\n\
+         <line x1=\"{left}\" y1=\"{top}\" x2=\"{left}\" y2=\"{bottom}\" \
+         stroke=\"#999\"/>",
+        left = plot_left,
+        right = plot_right,
+        top = plot_top,
+        bottom = plot_bottom,
+        zero = y(0),
+    )?;
+    for score in [min_score, 0, max_score] {
+        if score == 0 && min_score == 0 {
+            continue;
+        }
+        writeln!(
+            writer,
+            "<text x=\"{}\" y=\"{}\" text-anchor=\"end\">{}</text>",
+            plot_left - 6,
+            y(score) + 4,
+            score,
+        )?;
+    }
+    writeln!(
+        writer,
+        "<text x=\"{}\" y=\"{}\">{}</text>\n\
+         <text x=\"{}\" y=\"{}\" text-anchor=\"end\">{}</text>",
+        plot_left,
+        plot_bottom + 20,
+        format_timestamp(start),
+        plot_right,
+        plot_bottom + 20,
+        format_timestamp(end),
+    )?;
+
+    for (i, (name, (color, values))) in series.iter().enumerate() {
+        let mut points = Vec::new();
+        let mut last_score = None;
+        for &(index, score) in values {
+            let time = x(timeline.points[index].time);
+            if let Some(last_score) = last_score {
+                points.push(format!("{},{}", time, y(last_score)));
+            }
+            points.push(format!("{},{}", time, y(score)));
+            last_score = Some(score);
+        }
+        writeln!(
+            writer,
+            "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" \
+             stroke-width=\"2\"/>",
+            points.join(" "),
+            color,
+        )?;
+        let legend_y = plot_top + 10 + i as i64 * 18;
+        writeln!(
+            writer,
+            "<rect x=\"{}\" y=\"{}\" width=\"12\" height=\"12\" \
+             fill=\"{}\"/><text x=\"{}\" y=\"{}\">{}</text>",
+            plot_right + 16,
+            legend_y - 10,
+            color,
+            plot_right + 34,
+            legend_y,
+            label(name),
+        )?;
+    }
+    writeln!(writer, "</svg>")?;
+    Ok(())
+}
+
+/// Draws a horizontal bar chart of the kills by each weapon as an SVG image,
+/// from the most to the least used weapon. Weapons without kills are left
+/// out, and if there are no kills at all, a placeholder text is drawn
+/// instead of bars.
+pub fn write_weapon_usage<W>(
+    kills_by_weapon: &IndexMap<Weapon, KillCount>,
+    mut writer: W,
+) -> Result<()>
+where
+    W: Write,
+{
+    let mut weapons: Vec<_> = kills_by_weapon
+        .iter()
+        .filter(|(_, &kills)| kills > 0)
+        .map(|(&weapon, &kills)| (weapon, kills))
+        .collect();
+    weapons.sort_by(|(_, left), (_, right)| right.cmp(left));
+    let max_kills = weapons.first().map_or(1, |&(_, kills)| kills);
+    let bar_space = WEAPON_WIDTH - WEAPON_LABEL_WIDTH - 40;
+
+    write_svg_start(
+        &mut writer,
+        WEAPON_WIDTH,
+        weapons.len().max(1) * WEAPON_BAR_HEIGHT,
+        "Kills by weapon",
+    )?;
+    if weapons.is_empty() {
+        writeln!(
+            writer,
+            "<text x=\"0\" y=\"15\" fill=\"#666\">No kills</text>",
+        )?;
+    }
+    for (i, (weapon, kills)) in weapons.into_iter().enumerate() {
+        let y = i * WEAPON_BAR_HEIGHT;
+        let width = (kills as usize * bar_space / max_kills as usize).max(1);
+        writeln!(
+            writer,
+            "<text x=\"0\" y=\"{}\">{}</text>\
+             <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" \
+             fill=\"{}\"/><text x=\"{}\" y=\"{}\">{}</text>",
+            y + 15,
+            weapon,
+            WEAPON_LABEL_WIDTH,
+            y + 3,
+            width,
+            WEAPON_BAR_HEIGHT - 6,
+            BAR_COLOR,
+            WEAPON_LABEL_WIDTH + width + 4,
+            y + 15,
+            kills,
+        )?;
+    }
+    writeln!(writer, "</svg>")?;
+    Ok(())
+}
+
+/// Draws a heatmap of the given kill matrix as an SVG image: a row per
+/// killer and a column per target, each cell as opaque as its share of the
+/// highest count.
+pub fn write_kill_heatmap<W>(matrix: &KillMatrix, mut writer: W) -> Result<()>
+where
+    W: Write,
+{
+    let players: Vec<_> = matrix.players().collect();
+    let max_kills = players
+        .iter()
+        .flat_map(|killer| {
+            players.iter().map(|target| matrix.get(killer, target))
+        })
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    let size = HEATMAP_LABEL_SIZE + players.len() * CELL_SIZE;
+
+    write_svg_start(&mut writer, size, size, "Kills by killer and target")?;
+    writeln!(
+        writer,
+        "<rect width=\"100%\" height=\"100%\" fill=\"white\"/>\n\
+         <text x=\"4\" y=\"{}\" fill=\"#666\">killer \\ target</text>",
+        HEATMAP_LABEL_SIZE - 6,
+    )?;
+    for (i, player) in players.iter().enumerate() {
+        let offset = HEATMAP_LABEL_SIZE + i * CELL_SIZE + CELL_SIZE / 2;
+        writeln!(
+            writer,
+            "<text x=\"{x}\" y=\"{y}\" text-anchor=\"end\">{name}</text>\
+             <text x=\"{offset}\" y=\"{label}\" \
+             transform=\"rotate(-45 {offset} {label})\">{name}</text>",
+            x = HEATMAP_LABEL_SIZE - 6,
+            y = offset + 4,
+            offset = offset,
+            label = HEATMAP_LABEL_SIZE - 6,
+            name = label(player),
+        )?;
+    }
+    for (row, killer) in players.iter().enumerate() {
+        for (column, target) in players.iter().enumerate() {
+            let kills = matrix.get(killer, target);
+            let x = HEATMAP_LABEL_SIZE + column * CELL_SIZE;
+            let y = HEATMAP_LABEL_SIZE + row * CELL_SIZE;
+            writeln!(
+                writer,
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" \
+                 fill=\"{}\" fill-opacity=\"{:.2}\" stroke=\"#ddd\"/>",
+                x,
+                y,
+                CELL_SIZE,
+                CELL_SIZE,
+                BAR_COLOR,
+                kills.max(0) as f64 / max_kills as f64,
+            )?;
+            if kills != 0 {
+                writeln!(
+                    writer,
+                    "<text x=\"{}\" y=\"{}\" text-anchor=\"middle\">{}</text>",
+                    x + CELL_SIZE / 2,
+                    y + CELL_SIZE / 2 + 4,
+                    kills,
+                )?;
+            }
+        }
+    }
+    writeln!(writer, "</svg>")?;
+    Ok(())
+}
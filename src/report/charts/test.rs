@@ -0,0 +1,169 @@
+use super::{
+    escape_xml,
+    write_kill_heatmap,
+    write_score_timeline,
+    write_weapon_usage,
+    Chart,
+};
+use crate::{
+    game::{Game, GameType, Kill, Killer, MeansOfDeath, PlayerName, Team},
+    report::{
+        matrix::KillMatrix,
+        timeline::TimelineConfig,
+        GameReport,
+        ReportConfig,
+    },
+};
+use std::collections::HashMap;
+
+fn game(game_type: GameType) -> Game {
+    Game {
+        game_type,
+        start_time: 60,
+        players: HashMap::from([
+            (2, PlayerName::from("^1Zeh")),
+            (3, PlayerName::from("Tom & Jerry")),
+        ]),
+        teams: HashMap::from([(2, Team::Red), (3, Team::Blue)]),
+        kills: vec![
+            Kill {
+                killer: Killer::Player(2),
+                target: 3,
+                means: MeansOfDeath::from("MOD_RAILGUN"),
+                time: 2 * 60,
+            },
+            Kill {
+                killer: Killer::Player(3),
+                target: 2,
+                means: MeansOfDeath::from("MOD_ROCKET"),
+                time: 3 * 60,
+            },
+            Kill {
+                killer: Killer::Player(2),
+                target: 3,
+                means: MeansOfDeath::from("MOD_RAILGUN"),
+                time: 4 * 60,
+            },
+        ],
+        ..Game::default()
+    }
+}
+
+fn report(game_type: GameType) -> GameReport {
+    let config = ReportConfig {
+        timeline: Some(TimelineConfig::default()),
+        ..ReportConfig::default()
+    };
+    GameReport::generate_with(&game(game_type), &config).unwrap()
+}
+
+fn draw<F>(draw: F) -> String
+where
+    F: FnOnce(&mut Vec<u8>) -> crate::error::Result<()>,
+{
+    let mut output = Vec::new();
+    draw(&mut output).unwrap();
+    let svg = String::from_utf8(output).unwrap();
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+    assert!(svg.ends_with("</svg>\n"));
+    svg
+}
+
+#[test]
+fn escape_special_characters() {
+    assert_eq!(
+        escape_xml("<Tom & 'Jerry'>"),
+        "&lt;Tom &amp; &#39;Jerry&#39;&gt;",
+    );
+    assert_eq!(
+        escape_xml("<a href=\"x\">Tom & 'Jerry'</a>"),
+        "&lt;a href=&quot;x&quot;&gt;Tom &amp; &#39;Jerry&#39;&lt;/a&gt;",
+    );
+}
+
+#[test]
+fn draw_player_timeline() {
+    let report = report(GameType::FreeForAll);
+    let svg = draw(|output| {
+        write_score_timeline(report.timeline.as_ref().unwrap(), output)
+    });
+    assert_eq!(svg.matches("<polyline").count(), 2);
+    assert!(svg.contains(">Zeh</text>"));
+    assert!(svg.contains(">Tom &amp; Jerry</text>"));
+    assert!(svg.contains(">1:00</text>"));
+    assert!(svg.contains(">4:00</text>"));
+}
+
+#[test]
+fn draw_timeline_of_players_with_same_stripped_name() {
+    let mut game = game(GameType::FreeForAll);
+    game.players.insert(3, PlayerName::from("^4Zeh"));
+    let config = ReportConfig {
+        timeline: Some(TimelineConfig::default()),
+        ..ReportConfig::default()
+    };
+    let report = GameReport::generate_with(&game, &config).unwrap();
+    let svg = draw(|output| {
+        write_score_timeline(report.timeline.as_ref().unwrap(), output)
+    });
+    assert_eq!(svg.matches("<polyline").count(), 2);
+    assert_eq!(svg.matches(">Zeh</text>").count(), 2);
+}
+
+#[test]
+fn draw_team_timeline() {
+    let report = report(GameType::TeamDeathmatch);
+    let svg = draw(|output| {
+        write_score_timeline(report.timeline.as_ref().unwrap(), output)
+    });
+    assert_eq!(svg.matches("<polyline").count(), 2);
+    assert!(svg.contains(">red</text>"));
+    assert!(svg.contains(">blue</text>"));
+    assert!(!svg.contains(">Zeh</text>"));
+}
+
+#[test]
+fn draw_weapon_usage() {
+    let report = report(GameType::FreeForAll);
+    let svg =
+        draw(|output| write_weapon_usage(&report.kills_by_weapon, output));
+    assert_eq!(svg.matches("<rect").count(), 2);
+    let railgun = svg.find(">railgun</text>").unwrap();
+    let rocket = svg.find(">rocket_launcher</text>").unwrap();
+    assert!(railgun < rocket);
+}
+
+#[test]
+fn draw_weapon_usage_without_kills() {
+    let report = GameReport::generate(&Game::default()).unwrap();
+    let svg =
+        draw(|output| write_weapon_usage(&report.kills_by_weapon, output));
+    assert!(svg.contains("height=\"22\""));
+    assert!(svg.contains(">No kills</text>"));
+    assert!(!svg.contains("<rect"));
+}
+
+#[test]
+fn draw_kill_heatmap() {
+    let mut matrix = KillMatrix::new();
+    matrix.insert_player("Zeh");
+    matrix.add("Zeh", "Mal", 2);
+    matrix.add("Mal", "Zeh", 1);
+    let svg = draw(|output| write_kill_heatmap(&matrix, output));
+    assert_eq!(svg.matches("fill-opacity").count(), 4);
+    assert!(svg.contains("fill-opacity=\"1.00\""));
+    assert!(svg.contains("fill-opacity=\"0.50\""));
+    assert!(svg.contains("fill-opacity=\"0.00\""));
+}
+
+#[test]
+fn render_every_chart() {
+    let report = report(GameType::FreeForAll);
+    for &chart in Chart::ALL {
+        draw(|output| chart.render(&report, output));
+    }
+
+    let report = GameReport::generate(&game(GameType::FreeForAll)).unwrap();
+    let mut output = Vec::new();
+    assert!(Chart::ScoreTimeline.render(&report, &mut output).is_err());
+}
@@ -0,0 +1,160 @@
+//! This module exposes capture the flag statistics: flag possessions,
+//! captures, returns and kills of flag carriers, reconstructed from the flag
+//! events of a game.
+
+use crate::game::{FlagAction, Game, Killer, PlayerName, Team, Timestamp};
+use indexmap::IndexMap;
+
+#[cfg(test)]
+mod test;
+
+/// Count of flag actions, e.g. captures of a player.
+pub type FlagCount = u64;
+
+/// How a flag possession ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PossessionOutcome {
+    /// The carrier captured the flag.
+    Captured,
+    /// The carrier died and dropped the flag.
+    Dropped,
+    /// The carrier was still holding the flag when the game ended.
+    Held,
+}
+
+/// An interval during which a player carried the enemy flag.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct FlagPossession {
+    /// The player who carried the flag.
+    pub player: PlayerName,
+    /// The flag carried, identified by the team that owns it.
+    pub flag: Team,
+    /// When the flag was picked up.
+    pub start: Timestamp,
+    /// When the flag was captured or dropped, `None` if it was still held.
+    pub end: Option<Timestamp>,
+    /// How the possession ended.
+    pub outcome: PossessionOutcome,
+}
+
+/// Capture the flag statistics of a single player.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize)]
+pub struct CtfPlayerStats {
+    /// Times the player picked up the enemy flag.
+    pub pickups: FlagCount,
+    /// Times the player captured the enemy flag.
+    pub captures: FlagCount,
+    /// Times the player returned their own dropped flag.
+    pub returns: FlagCount,
+    /// Times the player killed an enemy flag carrier.
+    pub carrier_kills: FlagCount,
+    /// Times the player died carrying the enemy flag.
+    pub drops: FlagCount,
+}
+
+/// Capture the flag statistics of a single game.
+#[derive(Debug, Clone, PartialEq, Eq, Default, serde::Serialize)]
+pub struct CtfReport {
+    /// Flag possessions in the order they started.
+    pub possessions: Vec<FlagPossession>,
+    /// The mapping of player names to their statistics.
+    pub players: IndexMap<PlayerName, CtfPlayerStats>,
+    /// The mapping of teams to how many times they captured the enemy flag.
+    pub team_captures: IndexMap<Team, FlagCount>,
+}
+
+impl CtfReport {
+    /// Generates the capture the flag statistics of the given game. Flag
+    /// events involving players without a known name are ignored.
+    pub fn generate(game: &Game) -> Self {
+        let mut this = Self {
+            players: game
+                .players
+                .values()
+                .cloned()
+                .map(|name| (name, CtfPlayerStats::default()))
+                .collect(),
+            team_captures: IndexMap::from([(Team::Red, 0), (Team::Blue, 0)]),
+            ..Self::default()
+        };
+        let mut open_possessions = IndexMap::<Team, usize>::new();
+
+        for event in &game.flag_events {
+            let Some(player) = game.players.get(&event.player) else {
+                continue;
+            };
+            match event.action {
+                FlagAction::Pickup => {
+                    open_possessions.insert(event.flag, this.possessions.len());
+                    this.possessions.push(FlagPossession {
+                        player: player.clone(),
+                        flag: event.flag,
+                        start: event.time,
+                        end: None,
+                        outcome: PossessionOutcome::Held,
+                    });
+                    this.stats_mut(player).pickups += 1;
+                },
+                FlagAction::Capture => {
+                    this.close(
+                        &mut open_possessions,
+                        event.flag,
+                        event.time,
+                        PossessionOutcome::Captured,
+                    );
+                    this.stats_mut(player).captures += 1;
+                    let capturing_team = event.flag.opponent();
+                    if let Some(captures) = capturing_team
+                        .and_then(|team| this.team_captures.get_mut(&team))
+                    {
+                        *captures += 1;
+                    }
+                },
+                FlagAction::Return => {
+                    this.stats_mut(player).returns += 1;
+                },
+                FlagAction::Drop { killer } => {
+                    this.close(
+                        &mut open_possessions,
+                        event.flag,
+                        event.time,
+                        PossessionOutcome::Dropped,
+                    );
+                    this.stats_mut(player).drops += 1;
+                    let killer = match killer {
+                        Some(Killer::Player(id)) if id != event.player => {
+                            game.players.get(&id)
+                        },
+                        _ => None,
+                    };
+                    if let Some(killer) = killer {
+                        this.stats_mut(killer).carrier_kills += 1;
+                    }
+                },
+            }
+        }
+
+        this
+    }
+
+    /// Statistics of the given player, created if missing.
+    fn stats_mut(&mut self, player: &str) -> &mut CtfPlayerStats {
+        self.players.entry(PlayerName::from(player)).or_default()
+    }
+
+    /// Ends the open possession of the given flag, if any.
+    fn close(
+        &mut self,
+        open_possessions: &mut IndexMap<Team, usize>,
+        flag: Team,
+        time: Timestamp,
+        outcome: PossessionOutcome,
+    ) {
+        if let Some(index) = open_possessions.swap_remove(&flag) {
+            let possession = &mut self.possessions[index];
+            possession.end = Some(time);
+            possession.outcome = outcome;
+        }
+    }
+}
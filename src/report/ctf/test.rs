@@ -0,0 +1,124 @@
+use super::{CtfPlayerStats, CtfReport, FlagPossession, PossessionOutcome};
+use crate::game::{
+    FlagAction,
+    FlagEvent,
+    Game,
+    GameType,
+    Killer,
+    PlayerId,
+    PlayerName,
+    Team,
+    Timestamp,
+};
+use indexmap::IndexMap;
+use std::collections::HashMap;
+
+fn flag_event(
+    flag: Team,
+    action: FlagAction,
+    player: PlayerId,
+    time: Timestamp,
+) -> FlagEvent {
+    FlagEvent { flag, action, player, time }
+}
+
+fn game() -> Game {
+    Game {
+        game_type: GameType::CaptureTheFlag,
+        players: HashMap::from([
+            (2, PlayerName::from("Zeh")),
+            (3, PlayerName::from("Mal")),
+            (4, PlayerName::from("Isgalamido")),
+        ]),
+        teams: HashMap::from([(2, Team::Red), (3, Team::Red), (4, Team::Blue)]),
+        flag_events: vec![
+            flag_event(Team::Blue, FlagAction::Pickup, 2, 10),
+            flag_event(
+                Team::Blue,
+                FlagAction::Drop { killer: Some(Killer::Player(4)) },
+                2,
+                15,
+            ),
+            flag_event(Team::Red, FlagAction::Pickup, 4, 16),
+            flag_event(Team::Blue, FlagAction::Pickup, 3, 20),
+            flag_event(Team::Blue, FlagAction::Capture, 3, 30),
+            flag_event(
+                Team::Red,
+                FlagAction::Drop { killer: Some(Killer::World) },
+                4,
+                32,
+            ),
+            flag_event(Team::Red, FlagAction::Return, 2, 33),
+            flag_event(Team::Blue, FlagAction::Pickup, 2, 40),
+        ],
+        ..Game::default()
+    }
+}
+
+#[test]
+fn possessions() {
+    let report = CtfReport::generate(&game());
+    let expected = vec![
+        FlagPossession {
+            player: PlayerName::from("Zeh"),
+            flag: Team::Blue,
+            start: 10,
+            end: Some(15),
+            outcome: PossessionOutcome::Dropped,
+        },
+        FlagPossession {
+            player: PlayerName::from("Isgalamido"),
+            flag: Team::Red,
+            start: 16,
+            end: Some(32),
+            outcome: PossessionOutcome::Dropped,
+        },
+        FlagPossession {
+            player: PlayerName::from("Mal"),
+            flag: Team::Blue,
+            start: 20,
+            end: Some(30),
+            outcome: PossessionOutcome::Captured,
+        },
+        FlagPossession {
+            player: PlayerName::from("Zeh"),
+            flag: Team::Blue,
+            start: 40,
+            end: None,
+            outcome: PossessionOutcome::Held,
+        },
+    ];
+    assert_eq!(expected, report.possessions);
+}
+
+#[test]
+fn player_and_team_counts() {
+    let report = CtfReport::generate(&game());
+    let expected = CtfPlayerStats {
+        pickups: 2,
+        captures: 0,
+        returns: 1,
+        carrier_kills: 0,
+        drops: 1,
+    };
+    assert_eq!(expected, report.players["Zeh"]);
+    let expected = CtfPlayerStats {
+        pickups: 1,
+        captures: 1,
+        returns: 0,
+        carrier_kills: 0,
+        drops: 0,
+    };
+    assert_eq!(expected, report.players["Mal"]);
+    let expected = CtfPlayerStats {
+        pickups: 1,
+        captures: 0,
+        returns: 0,
+        carrier_kills: 1,
+        drops: 1,
+    };
+    assert_eq!(expected, report.players["Isgalamido"]);
+
+    let expected = IndexMap::from([(Team::Red, 1), (Team::Blue, 0)]);
+    assert_eq!(expected, report.team_captures);
+}
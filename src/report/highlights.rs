@@ -0,0 +1,195 @@
+//! This module exposes the highlights of a game: first blood, killing
+//! streaks, multi-kills and shutdowns, computed from kill timestamps.
+
+use crate::game::{Game, KillCount, Killer, PlayerId, PlayerName, Timestamp};
+use indexmap::IndexMap;
+use std::collections::HashMap;
+
+#[cfg(test)]
+mod test;
+
+/// Configuration of highlight detection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HighlightsConfig {
+    /// Maximum time in seconds between two consecutive kills of a player for
+    /// them to be part of the same multi-kill.
+    pub multi_kill_window: Timestamp,
+    /// Minimum length of a streak for its end to be accounted as a shutdown.
+    pub min_shutdown_streak: KillCount,
+}
+
+impl Default for HighlightsConfig {
+    fn default() -> Self {
+        Self { multi_kill_window: 3, min_shutdown_streak: 3 }
+    }
+}
+
+/// The first kill of a player by another player in a game.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct FirstBlood {
+    /// The player who killed.
+    pub killer: PlayerName,
+    /// The player who was killed.
+    pub target: PlayerName,
+    /// When it happened.
+    pub time: Timestamp,
+}
+
+/// Consecutive kills by a player, each one within the multi-kill window of the
+/// previous one.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct MultiKill {
+    /// The player who killed.
+    pub player: PlayerName,
+    /// How many kills, at least `2`.
+    pub kills: KillCount,
+    /// Time of the first kill.
+    pub start: Timestamp,
+    /// Time of the last kill.
+    pub end: Timestamp,
+}
+
+impl MultiKill {
+    /// Conventional name of this multi-kill, e.g. "double kill".
+    pub fn label(&self) -> &'static str {
+        match self.kills {
+            2 => "double kill",
+            3 => "triple kill",
+            4 => "quad kill",
+            _ => "multi kill",
+        }
+    }
+}
+
+/// The end of a killing streak by the death of the player on the streak.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct Shutdown {
+    /// The player who ended the streak, `None` if it was the world or the
+    /// player on the streak themselves.
+    pub killer: Option<PlayerName>,
+    /// The player whose streak was ended.
+    pub target: PlayerName,
+    /// Length of the streak that was ended.
+    pub streak: KillCount,
+    /// When it happened.
+    pub time: Timestamp,
+}
+
+/// Highlights of a single game. A streak is a sequence of kills of other
+/// players without dying; suicides do not count as kills, but end streaks.
+#[derive(Debug, Clone, PartialEq, Eq, Default, serde::Serialize)]
+pub struct Highlights {
+    /// The first kill of a player by another player, if any.
+    pub first_blood: Option<FirstBlood>,
+    /// The mapping of player names to their longest killing streaks.
+    pub longest_streaks: IndexMap<PlayerName, KillCount>,
+    /// Multi-kills in the order they started.
+    pub multi_kills: Vec<MultiKill>,
+    /// Ended streaks at least as long as configured, in the order they ended.
+    pub shutdowns: Vec<Shutdown>,
+}
+
+/// A chain of kills of a player that could become a multi-kill.
+#[derive(Debug, Clone, Copy)]
+struct KillChain {
+    /// How many kills in the chain.
+    kills: KillCount,
+    /// Time of the first kill.
+    start: Timestamp,
+    /// Time of the last kill.
+    end: Timestamp,
+}
+
+impl Highlights {
+    /// Generates the highlights of the given game. Kills involving players
+    /// without a known name are ignored.
+    pub fn generate(game: &Game, config: &HighlightsConfig) -> Self {
+        let mut this = Self {
+            longest_streaks: game
+                .players
+                .values()
+                .cloned()
+                .map(|name| (name, 0))
+                .collect(),
+            ..Self::default()
+        };
+        let mut streaks = HashMap::<PlayerId, KillCount>::new();
+        let mut chains = HashMap::<PlayerId, KillChain>::new();
+
+        for kill in &game.kills {
+            let Some(target) = game.players.get(&kill.target) else {
+                continue;
+            };
+            let killer = match kill.killer {
+                Killer::Player(id) if id != kill.target => {
+                    game.players.get(&id).map(|name| (id, name))
+                },
+                _ => None,
+            };
+
+            if let Some((killer_id, killer)) = killer {
+                if this.first_blood.is_none() {
+                    this.first_blood = Some(FirstBlood {
+                        killer: killer.clone(),
+                        target: target.clone(),
+                        time: kill.time,
+                    });
+                }
+
+                let streak = streaks.entry(killer_id).or_default();
+                *streak += 1;
+                if let Some(longest) = this.longest_streaks.get_mut(killer) {
+                    *longest = (*longest).max(*streak);
+                }
+
+                let chain = chains.entry(killer_id).or_insert(KillChain {
+                    kills: 0,
+                    start: kill.time,
+                    end: kill.time,
+                });
+                if chain.kills > 0
+                    && kill.time.saturating_sub(chain.end)
+                        > config.multi_kill_window
+                {
+                    this.push_multi_kill(killer, *chain);
+                    *chain = KillChain { kills: 0, start: kill.time, end: 0 };
+                }
+                chain.kills += 1;
+                chain.end = kill.time;
+            }
+
+            let ended_streak = streaks.remove(&kill.target).unwrap_or(0);
+            if ended_streak > 0 && ended_streak >= config.min_shutdown_streak {
+                this.shutdowns.push(Shutdown {
+                    killer: killer.map(|(_, name)| name.clone()),
+                    target: target.clone(),
+                    streak: ended_streak,
+                    time: kill.time,
+                });
+            }
+        }
+
+        for (id, chain) in chains {
+            if let Some(player) = game.players.get(&id) {
+                this.push_multi_kill(player, chain);
+            }
+        }
+        this.multi_kills.sort_by(|left, right| {
+            (left.start, &left.player).cmp(&(right.start, &right.player))
+        });
+
+        this
+    }
+
+    /// Accounts a finished chain of kills, if it is a multi-kill.
+    fn push_multi_kill(&mut self, player: &str, chain: KillChain) {
+        if chain.kills >= 2 {
+            self.multi_kills.push(MultiKill {
+                player: PlayerName::from(player),
+                kills: chain.kills,
+                start: chain.start,
+                end: chain.end,
+            });
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use super::{FirstBlood, Highlights, HighlightsConfig, MultiKill, Shutdown};
+use crate::game::{Game, Kill, Killer, MeansOfDeath, PlayerName, Timestamp};
+use std::collections::HashMap;
+
+fn kill(killer: Killer, target: u32, time: Timestamp) -> Kill {
+    Kill { killer, target, means: MeansOfDeath::from("MOD_RAILGUN"), time }
+}
+
+fn game() -> Game {
+    Game {
+        players: HashMap::from([
+            (2, PlayerName::from("Zeh")),
+            (3, PlayerName::from("Mal")),
+            (4, PlayerName::from("Isgalamido")),
+        ]),
+        kills: vec![
+            kill(Killer::World, 3, 5),
+            kill(Killer::Player(2), 3, 10),
+            kill(Killer::Player(2), 4, 12),
+            kill(Killer::Player(2), 3, 15),
+            kill(Killer::Player(2), 4, 30),
+            kill(Killer::Player(3), 2, 31),
+            kill(Killer::Player(4), 3, 40),
+            kill(Killer::Player(4), 4, 41),
+        ],
+        ..Game::default()
+    }
+}
+
+fn generate(config: HighlightsConfig) -> Highlights {
+    Highlights::generate(&game(), &config)
+}
+
+#[test]
+fn first_blood_ignores_world() {
+    let highlights = generate(HighlightsConfig::default());
+    let expected = Some(FirstBlood {
+        killer: PlayerName::from("Zeh"),
+        target: PlayerName::from("Mal"),
+        time: 10,
+    });
+    assert_eq!(expected, highlights.first_blood);
+}
+
+#[test]
+fn longest_streaks() {
+    let highlights = generate(HighlightsConfig::default());
+    assert_eq!(highlights.longest_streaks["Zeh"], 4);
+    assert_eq!(highlights.longest_streaks["Mal"], 1);
+    assert_eq!(highlights.longest_streaks["Isgalamido"], 1);
+}
+
+#[test]
+fn multi_kills_within_window() {
+    let highlights = generate(HighlightsConfig::default());
+    let expected = vec![MultiKill {
+        player: PlayerName::from("Zeh"),
+        kills: 3,
+        start: 10,
+        end: 15,
+    }];
+    assert_eq!(expected, highlights.multi_kills);
+    assert_eq!(highlights.multi_kills[0].label(), "triple kill");
+
+    let config =
+        HighlightsConfig { multi_kill_window: 2, ..Default::default() };
+    let highlights = generate(config);
+    let expected = vec![MultiKill {
+        player: PlayerName::from("Zeh"),
+        kills: 2,
+        start: 10,
+        end: 12,
+    }];
+    assert_eq!(expected, highlights.multi_kills);
+}
+
+#[test]
+fn shutdowns() {
+    let highlights = generate(HighlightsConfig::default());
+    let expected = vec![Shutdown {
+        killer: Some(PlayerName::from("Mal")),
+        target: PlayerName::from("Zeh"),
+        streak: 4,
+        time: 31,
+    }];
+    assert_eq!(expected, highlights.shutdowns);
+
+    let config =
+        HighlightsConfig { min_shutdown_streak: 1, ..Default::default() };
+    let highlights = generate(config);
+    assert_eq!(highlights.shutdowns.len(), 3);
+    assert_eq!(highlights.shutdowns[2].killer, None);
+    assert_eq!(highlights.shutdowns[2].target, "Isgalamido");
+}
@@ -0,0 +1,344 @@
+//! This module exposes rendering of reports into a single, self-contained HTML
+//! file, meant to be published as is on a static site: styles, scripts and
+//! charts are inlined, so the page works offline.
+//!
+//! The page has an index of games, a scoreboard per game (sortable by clicking
+//! its headers), a chart of kills by weapon per game, and a page per player
+//! linking to the games they played.
+
+use super::{
+    charts::{self, escape_xml},
+    ranking::Ranking,
+    render::Renderable,
+    totals::{IdentityKey, PlayerTotals},
+    GameReport,
+};
+use crate::{
+    error::Result,
+    game::{format_timestamp, strip_color_codes, KillCount, PlayerName},
+};
+use indexmap::IndexMap;
+use std::io::Write;
+
+#[cfg(test)]
+mod test;
+
+/// Styles of the page.
+const STYLE: &str = "\
+body { font-family: sans-serif; margin: 2em auto; max-width: 60em; }
+table { border-collapse: collapse; margin: 1em 0; }
+th, td { border-bottom: 1px solid #ccc; padding: 0.3em 0.8em; }
+th { cursor: pointer; text-align: left; user-select: none; }
+td.number { text-align: right; }
+section { margin-bottom: 3em; }
+";
+
+/// Script sorting a table by the column whose header is clicked, numerically
+/// if every cell of the column is a number. Clicking again reverses the
+/// order.
+const SCRIPT: &str = "\
+document.querySelectorAll('table.sortable th').forEach(th => {
+  th.addEventListener('click', () => {
+    const table = th.closest('table');
+    const body = table.tBodies[0];
+    const column = th.cellIndex;
+    const rows = Array.from(body.rows);
+    const value = row => row.cells[column].dataset.value
+      ?? row.cells[column].textContent;
+    const numeric = rows.every(row => !isNaN(parseFloat(value(row))));
+    const ascending = th.dataset.order !== 'ascending';
+    rows.sort((left, right) => {
+      const [a, b] = [value(left), value(right)];
+      const order = numeric ? parseFloat(a) - parseFloat(b)
+        : a.localeCompare(b);
+      return ascending ? order : -order;
+    });
+    th.dataset.order = ascending ? 'ascending' : 'descending';
+    rows.forEach(row => body.appendChild(row));
+  });
+});
+";
+
+/// A game of the report in which a player played.
+#[derive(Debug, Clone, Copy)]
+struct PlayerGame<'report> {
+    /// Index of the game in the report.
+    game: usize,
+    /// Name the player used in the game.
+    name: &'report str,
+    /// Position of the player in the game's ranking.
+    position: usize,
+    /// Score of the player in the game.
+    score: KillCount,
+    /// Kills of other players in the game.
+    frags: KillCount,
+    /// Deaths of the player in the game.
+    deaths: KillCount,
+}
+
+/// Renderer of the HTML page of a report.
+struct Page<'report, W> {
+    /// Where the page is written to.
+    writer: W,
+    /// Game reports along with their names, in order.
+    games: Vec<(&'report str, &'report GameReport)>,
+    /// How players are identified across games, if known.
+    identity: Option<&'report IdentityKey>,
+    /// Games of each player, indexed by player identity, in order of first
+    /// appearance.
+    players: IndexMap<PlayerName, Vec<PlayerGame<'report>>>,
+}
+
+impl<'report, W> Page<'report, W>
+where
+    W: Write,
+{
+    /// Creates the renderer of the given report, grouping the games of each
+    /// player by identity, if known, or by name otherwise.
+    fn new<T>(report: &'report T, writer: W) -> Self
+    where
+        T: Renderable + ?Sized,
+    {
+        let mut page = Self {
+            writer,
+            games: report.game_reports(),
+            identity: report.player_totals().map(PlayerTotals::identity),
+            players: IndexMap::new(),
+        };
+        for (i, (_, game_report)) in page.games.iter().enumerate() {
+            for entry in &game_report.ranking.entries {
+                let identity = page.identify(&entry.player);
+                page.players.entry(identity).or_default().push(
+                    PlayerGame {
+                        game: i,
+                        name: &entry.player,
+                        position: entry.position,
+                        score: entry.score,
+                        frags: entry.frags,
+                        deaths: entry.deaths,
+                    },
+                );
+            }
+        }
+        page
+    }
+
+    /// Identity of the player with the given name, which is the name itself
+    /// if the identity key is not known.
+    fn identify(&self, name: &str) -> PlayerName {
+        match self.identity {
+            Some(identity) => identity.identify(name),
+            None => PlayerName::from(name),
+        }
+    }
+
+    /// Anchor of the section of the given game.
+    fn game_anchor(game: usize) -> String {
+        format!("game-{}", game + 1)
+    }
+
+    /// Anchor of the page of the player with the given name, if they have
+    /// one.
+    fn player_anchor(&self, name: &str) -> Option<String> {
+        let index = self.players.get_index_of(&self.identify(name))?;
+        Some(format!("player-{}", index + 1))
+    }
+
+    /// Writes the whole page.
+    fn write<T>(&mut self, report: &T) -> Result<()>
+    where
+        T: Renderable + ?Sized,
+    {
+        writeln!(self.writer, "<!DOCTYPE html>")?;
+        writeln!(self.writer, "<html lang=\"en\">")?;
+        writeln!(self.writer, "<head>")?;
+        writeln!(self.writer, "<meta charset=\"utf-8\">")?;
+        writeln!(self.writer, "<title>Quake III: Arena report</title>")?;
+        writeln!(self.writer, "<style>\n{}</style>", STYLE)?;
+        writeln!(self.writer, "</head>")?;
+        writeln!(self.writer, "<body>")?;
+        writeln!(self.writer, "<h1>Quake III: Arena report</h1>")?;
+
+        self.write_index()?;
+        for i in 0 .. self.games.len() {
+            self.write_game(i)?;
+        }
+        if let Some(ranking) = report.overall_ranking() {
+            writeln!(self.writer, "<section id=\"overall-ranking\">")?;
+            writeln!(self.writer, "<h2>Overall ranking</h2>")?;
+            self.write_ranking(ranking)?;
+            writeln!(self.writer, "</section>")?;
+        }
+        self.write_players(report)?;
+
+        writeln!(self.writer, "<script>\n{}</script>", SCRIPT)?;
+        writeln!(self.writer, "</body>")?;
+        writeln!(self.writer, "</html>")?;
+        Ok(())
+    }
+
+    /// Writes the index of games and players.
+    fn write_index(&mut self) -> Result<()> {
+        writeln!(self.writer, "<nav>")?;
+        writeln!(self.writer, "<h2>Games</h2>")?;
+        writeln!(self.writer, "<ol>")?;
+        for (i, (name, game_report)) in self.games.iter().enumerate() {
+            writeln!(
+                self.writer,
+                "<li><a href=\"#{}\">{}</a>: {}, {}, {}, {} kills</li>",
+                Self::game_anchor(i),
+                escape_xml(name),
+                escape_xml(game_report.map.as_deref().unwrap_or("unknown")),
+                game_report.game_type,
+                format_timestamp(game_report.duration),
+                game_report.total_kills,
+            )?;
+        }
+        writeln!(self.writer, "</ol>")?;
+        writeln!(self.writer, "<h2>Players</h2>")?;
+        writeln!(self.writer, "<ul>")?;
+        for (i, identity) in self.players.keys().enumerate() {
+            writeln!(
+                self.writer,
+                "<li><a href=\"#player-{}\">{}</a></li>",
+                i + 1,
+                escape_xml(&strip_color_codes(identity)),
+            )?;
+        }
+        writeln!(self.writer, "</ul>")?;
+        writeln!(self.writer, "</nav>")?;
+        Ok(())
+    }
+
+    /// Writes the section of the game with the given index.
+    fn write_game(&mut self, game: usize) -> Result<()> {
+        let (name, game_report) = self.games[game];
+        writeln!(self.writer, "<section id=\"{}\">", Self::game_anchor(game))?;
+        writeln!(self.writer, "<h2>{}</h2>", escape_xml(name))?;
+        writeln!(
+            self.writer,
+            "<p>Map: {}. Type: {}. Duration: {}. Total kills: {}.</p>",
+            escape_xml(game_report.map.as_deref().unwrap_or("unknown")),
+            game_report.game_type,
+            format_timestamp(game_report.duration),
+            game_report.total_kills,
+        )?;
+        self.write_ranking(&game_report.ranking)?;
+        let has_kills =
+            game_report.kills_by_weapon.values().any(|&kills| kills > 0);
+        if has_kills {
+            charts::write_weapon_usage(
+                &game_report.kills_by_weapon,
+                &mut self.writer,
+            )?;
+        }
+        writeln!(self.writer, "</section>")?;
+        Ok(())
+    }
+
+    /// Writes a sortable ranking table, linking each player to their page.
+    fn write_ranking(&mut self, ranking: &Ranking) -> Result<()> {
+        writeln!(self.writer, "<table class=\"sortable\">")?;
+        writeln!(
+            self.writer,
+            "<thead><tr><th>#</th><th>Player</th><th>Score</th>\
+             <th>Frags</th><th>Deaths</th><th>K/D</th></tr></thead>",
+        )?;
+        writeln!(self.writer, "<tbody>")?;
+        for entry in &ranking.entries {
+            let name = escape_xml(&strip_color_codes(&entry.player));
+            let player = match self.player_anchor(&entry.player) {
+                Some(anchor) => format!("<a href=\"#{}\">{}</a>", anchor, name),
+                None => name,
+            };
+            writeln!(
+                self.writer,
+                "<tr><td class=\"number\">{}</td><td>{}</td>\
+                 <td class=\"number\">{}</td><td class=\"number\">{}</td>\
+                 <td class=\"number\">{}</td>\
+                 <td class=\"number\">{:.2}</td></tr>",
+                entry.position,
+                player,
+                entry.score,
+                entry.frags,
+                entry.deaths,
+                entry.kill_death_ratio,
+            )?;
+        }
+        writeln!(self.writer, "</tbody>")?;
+        writeln!(self.writer, "</table>")?;
+        Ok(())
+    }
+
+    /// Writes the page of each player: their totals across games, if known,
+    /// and the games they played.
+    fn write_players<T>(&mut self, report: &T) -> Result<()>
+    where
+        T: Renderable + ?Sized,
+    {
+        for (i, (identity, games)) in self.players.iter().enumerate() {
+            writeln!(self.writer, "<section id=\"player-{}\">", i + 1)?;
+            writeln!(
+                self.writer,
+                "<h2>{}</h2>",
+                escape_xml(&strip_color_codes(identity)),
+            )?;
+            let total = report
+                .player_totals()
+                .and_then(|totals| totals.players.get(identity));
+            if let Some(total) = total {
+                writeln!(
+                    self.writer,
+                    "<p>Games: {}. Wins: {}. Total score: {}. Average score: \
+                     {:.2}. Frags: {}. Deaths: {}.</p>",
+                    total.games_played,
+                    total.wins,
+                    total.total_score,
+                    total.average_score,
+                    total.frags,
+                    total.deaths,
+                )?;
+            }
+            writeln!(self.writer, "<table class=\"sortable\">")?;
+            writeln!(
+                self.writer,
+                "<thead><tr><th>Game</th><th>Name</th><th>Position</th>\
+                 <th>Score</th><th>Frags</th><th>Deaths</th></tr></thead>",
+            )?;
+            writeln!(self.writer, "<tbody>")?;
+            for played in games {
+                let (game_name, _) = self.games[played.game];
+                writeln!(
+                    self.writer,
+                    "<tr><td data-value=\"{}\"><a href=\"#{}\">{}</a></td>\
+                     <td>{}</td><td class=\"number\">{}</td>\
+                     <td class=\"number\">{}</td><td class=\"number\">{}</td>\
+                     <td class=\"number\">{}</td></tr>",
+                    played.game + 1,
+                    Self::game_anchor(played.game),
+                    escape_xml(game_name),
+                    escape_xml(&strip_color_codes(played.name)),
+                    played.position,
+                    played.score,
+                    played.frags,
+                    played.deaths,
+                )?;
+            }
+            writeln!(self.writer, "</tbody>")?;
+            writeln!(self.writer, "</table>")?;
+            writeln!(self.writer, "</section>")?;
+        }
+        Ok(())
+    }
+}
+
+/// Renders the given report as a self-contained HTML page into the given
+/// writer.
+pub fn render_html<T, W>(report: &T, writer: W) -> Result<()>
+where
+    T: Renderable + ?Sized,
+    W: Write,
+{
+    let mut page = Page::new(report, writer);
+    page.write(report)
+}
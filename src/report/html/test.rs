@@ -0,0 +1,97 @@
+use super::render_html;
+use crate::{
+    game::{Game, Kill, Killer, MeansOfDeath, PlayerName},
+    report::{totals::IdentityKey, GameReport, LogReport, ReportConfig},
+};
+use std::collections::HashMap;
+
+fn games() -> Vec<Game> {
+    let first = Game {
+        map: Some(String::from("q3dm17")),
+        players: HashMap::from([
+            (2, PlayerName::from("^1Zeh")),
+            (3, PlayerName::from("<b>Mal</b>")),
+        ]),
+        kills: vec![Kill {
+            killer: Killer::Player(2),
+            target: 3,
+            means: MeansOfDeath::from("MOD_RAILGUN"),
+            time: 60,
+        }],
+        ..Game::default()
+    };
+    let second = Game {
+        players: HashMap::from([(4, PlayerName::from("Zeh"))]),
+        ..Game::default()
+    };
+    vec![first, second]
+}
+
+fn render<T>(report: &T) -> String
+where
+    T: super::Renderable + ?Sized,
+{
+    let mut output = Vec::new();
+    render_html(report, &mut output).unwrap();
+    String::from_utf8(output).unwrap()
+}
+
+/// Targets of the links of the page, which must all be anchors of the page.
+fn link_targets(page: &str) -> Vec<&str> {
+    page.split("href=\"#")
+        .skip(1)
+        .map(|tail| &tail[.. tail.find('"').unwrap()])
+        .collect()
+}
+
+#[test]
+fn render_log_report() {
+    let config = ReportConfig {
+        identity: IdentityKey::StrippedName,
+        ..ReportConfig::default()
+    };
+    let game_iter = games().into_iter().map(Ok);
+    let report = LogReport::generate_with(game_iter, &config).unwrap();
+    let page = render(&report);
+
+    assert!(page.starts_with("<!DOCTYPE html>\n"));
+    assert!(page.ends_with("</html>\n"));
+    assert!(page.contains(
+        "<a href=\"#game-1\">game_1</a>: q3dm17, free_for_all, ",
+    ));
+    assert!(page.contains("Map: q3dm17. Type: free_for_all. "));
+    assert!(page.contains("<a href=\"#game-2\">game_2</a>: unknown"));
+    assert!(page.contains("<section id=\"overall-ranking\">"));
+    assert!(page.contains("&lt;b&gt;Mal&lt;/b&gt;"));
+    assert!(!page.contains("<b>Mal"));
+    assert!(page.contains("aria-label=\"Kills by weapon\""));
+    assert!(page.contains(">railgun</text>"));
+
+    // Both names of Zeh lead to the same player page, which links to both
+    // games.
+    assert_eq!(page.matches("<section id=\"player-").count(), 2);
+    let zeh = &page[page.find("<section id=\"player-1\">").unwrap() ..];
+    let zeh = &zeh[.. zeh.find("</section>").unwrap()];
+    assert!(zeh.contains("<h2>Zeh</h2>"));
+    assert!(zeh.contains("Games: 2."));
+    assert!(zeh.contains("href=\"#game-1\""));
+    assert!(zeh.contains("href=\"#game-2\""));
+
+    for target in link_targets(&page) {
+        assert!(page.contains(&format!("id=\"{}\"", target)), "{}", target);
+    }
+    assert!(!page.contains("src="));
+    assert!(!page.contains("<link"));
+}
+
+#[test]
+fn render_game_report() {
+    let game_report = GameReport::generate(&games()[0]).unwrap();
+    let page = render(&game_report);
+    assert!(page.contains("<a href=\"#game-1\">game</a>"));
+    assert!(!page.contains("overall-ranking"));
+    assert!(!page.contains("Games: "));
+    for target in link_targets(&page) {
+        assert!(page.contains(&format!("id=\"{}\"", target)), "{}", target);
+    }
+}
@@ -0,0 +1,136 @@
+//! This module exposes the head-to-head kill matrix, counting how many times
+//! each player killed each other player.
+
+use crate::game::{Game, KillCount, Killer, PlayerName};
+use indexmap::IndexMap;
+
+#[cfg(test)]
+mod test;
+
+/// Head-to-head kill counts between players. It is square: every player known
+/// by the matrix has a row (as a killer) and a column (as a target) for every
+/// other player, including themselves (suicides). Kills by the world are not
+/// accounted, since the world is not a player.
+///
+/// By default, it is serialized as a nested map `killer -> target -> kills`.
+/// For a flat list of edges, see [`KillMatrix::edges`].
+#[derive(Debug, Clone, PartialEq, Eq, Default, serde::Serialize)]
+#[serde(transparent)]
+pub struct KillMatrix {
+    /// Rows indexed by killer, columns indexed by target.
+    rows: IndexMap<PlayerName, IndexMap<PlayerName, KillCount>>,
+}
+
+impl KillMatrix {
+    /// Creates an empty matrix, with no players.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Generates the kill matrix of a single game.
+    pub fn generate(game: &Game) -> Self {
+        let mut this = Self::new();
+
+        for name in game.players.values() {
+            this.insert_player(name);
+        }
+
+        for kill in &game.kills {
+            let Killer::Player(killer_id) = kill.killer else {
+                continue;
+            };
+            let (Some(killer), Some(target)) =
+                (game.players.get(&killer_id), game.players.get(&kill.target))
+            else {
+                log::warn!(
+                    "Kill matrix: unnamed player in kill {:?} ignored",
+                    kill
+                );
+                continue;
+            };
+            this.add(killer, target, 1);
+        }
+
+        this
+    }
+
+    /// Inserts a player with no kills, if they are not in the matrix yet.
+    pub fn insert_player(&mut self, name: &str) {
+        if self.rows.contains_key(name) {
+            return;
+        }
+        for row in self.rows.values_mut() {
+            row.insert(PlayerName::from(name), 0);
+        }
+        let mut row: IndexMap<_, _> =
+            self.rows.keys().cloned().map(|target| (target, 0)).collect();
+        row.insert(PlayerName::from(name), 0);
+        self.rows.insert(PlayerName::from(name), row);
+    }
+
+    /// Adds the given count of kills of the target by the killer, inserting
+    /// players as needed.
+    pub fn add(&mut self, killer: &str, target: &str, count: KillCount) {
+        self.insert_player(killer);
+        self.insert_player(target);
+        if let Some(kills) =
+            self.rows.get_mut(killer).and_then(|row| row.get_mut(target))
+        {
+            *kills += count;
+        }
+    }
+
+    /// Merges another matrix into this one, summing the kill counts. Used to
+    /// aggregate the matrices of many games.
+    pub fn merge(&mut self, other: &Self) {
+        for name in other.players() {
+            self.insert_player(name);
+        }
+        for edge in other.edges() {
+            self.add(&edge.killer, &edge.target, edge.kills);
+        }
+    }
+
+    /// Number of times the killer killed the target. Unknown players have
+    /// zero kills.
+    pub fn get(&self, killer: &str, target: &str) -> KillCount {
+        self.rows
+            .get(killer)
+            .and_then(|row| row.get(target))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Iterates over the players of this matrix, in insertion order.
+    pub fn players(&self) -> impl Iterator<Item = &PlayerName> + '_ {
+        self.rows.keys()
+    }
+
+    /// Flat list of edges with non-zero kill counts, friendly to `serde` as an
+    /// alternative representation of the matrix.
+    pub fn edges(&self) -> Vec<KillEdge> {
+        self.rows
+            .iter()
+            .flat_map(|(killer, row)| {
+                row.iter().filter(|(_, &kills)| kills != 0).map(
+                    move |(target, &kills)| KillEdge {
+                        killer: killer.clone(),
+                        target: target.clone(),
+                        kills,
+                    },
+                )
+            })
+            .collect()
+    }
+}
+
+/// A single entry of the flat representation of a [`KillMatrix`].
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct KillEdge {
+    /// The player who killed.
+    pub killer: PlayerName,
+    /// The player who was killed.
+    pub target: PlayerName,
+    /// How many times the killer killed the target.
+    pub kills: KillCount,
+}
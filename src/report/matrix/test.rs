@@ -0,0 +1,104 @@
+use super::{KillEdge, KillMatrix};
+use crate::game::{Game, Kill, Killer, MeansOfDeath, PlayerName};
+use std::collections::HashMap;
+
+fn game() -> Game {
+    Game {
+        players: HashMap::from([
+            (2, PlayerName::from("Dono da Bola")),
+            (3, PlayerName::from("Isgalamido")),
+            (4, PlayerName::from("Zeh")),
+        ]),
+        kills: vec![
+            Kill {
+                killer: Killer::Player(2),
+                target: 4,
+                means: MeansOfDeath::from("MOD_ROCKET"),
+                time: 10,
+            },
+            Kill {
+                killer: Killer::World,
+                target: 2,
+                means: MeansOfDeath::from("MOD_FALLING"),
+                time: 12,
+            },
+            Kill {
+                killer: Killer::Player(4),
+                target: 3,
+                means: MeansOfDeath::from("MOD_RAILGUN"),
+                time: 20,
+            },
+            Kill {
+                killer: Killer::Player(2),
+                target: 4,
+                means: MeansOfDeath::from("MOD_SHOTGUN"),
+                time: 31,
+            },
+            Kill {
+                killer: Killer::Player(3),
+                target: 3,
+                means: MeansOfDeath::from("MOD_ROCKET_SPLASH"),
+                time: 45,
+            },
+        ],
+        ..Game::default()
+    }
+}
+
+#[test]
+fn generate_counts_pairs() {
+    let matrix = KillMatrix::generate(&game());
+    assert_eq!(matrix.get("Dono da Bola", "Zeh"), 2);
+    assert_eq!(matrix.get("Zeh", "Isgalamido"), 1);
+    assert_eq!(matrix.get("Isgalamido", "Isgalamido"), 1);
+    assert_eq!(matrix.get("Zeh", "Dono da Bola"), 0);
+    assert_eq!(matrix.get("Nobody", "Zeh"), 0);
+}
+
+#[test]
+fn generate_is_square() {
+    let matrix = KillMatrix::generate(&game());
+    assert_eq!(matrix.rows.len(), 3);
+    for row in matrix.rows.values() {
+        assert_eq!(row.len(), 3);
+    }
+}
+
+#[test]
+fn edges_skip_zeros() {
+    let mut expected = vec![
+        KillEdge {
+            killer: PlayerName::from("Dono da Bola"),
+            target: PlayerName::from("Zeh"),
+            kills: 2,
+        },
+        KillEdge {
+            killer: PlayerName::from("Isgalamido"),
+            target: PlayerName::from("Isgalamido"),
+            kills: 1,
+        },
+        KillEdge {
+            killer: PlayerName::from("Zeh"),
+            target: PlayerName::from("Isgalamido"),
+            kills: 1,
+        },
+    ];
+    let mut actual = KillMatrix::generate(&game()).edges();
+    let key = |edge: &KillEdge| (edge.killer.clone(), edge.target.clone());
+    expected.sort_by_key(key);
+    actual.sort_by_key(key);
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn merge_sums_and_inserts_players() {
+    let mut matrix = KillMatrix::generate(&game());
+    let mut other = KillMatrix::new();
+    other.add("Zeh", "Mal", 3);
+    other.add("Dono da Bola", "Zeh", 1);
+    matrix.merge(&other);
+    assert_eq!(matrix.get("Dono da Bola", "Zeh"), 3);
+    assert_eq!(matrix.get("Zeh", "Mal"), 3);
+    assert_eq!(matrix.get("Mal", "Isgalamido"), 0);
+    assert_eq!(matrix.players().count(), 4);
+}
@@ -0,0 +1,117 @@
+//! This module exposes player rankings, sorted by configurable keys and with
+//! competition-style ties (1, 2, 2, 4).
+
+use super::stats::PlayerStats;
+use crate::game::{KillCount, PlayerName};
+use std::cmp::Ordering;
+
+#[cfg(test)]
+mod test;
+
+/// Position of a player in a ranking, starting at `1`. Tied players share the
+/// same position, and the following position is skipped for each tie.
+pub type Position = usize;
+
+/// A key by which players are ranked. Higher values rank first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RankingKey {
+    /// Score in terms of killing, discounting deaths by the "world".
+    Score,
+    /// Kills of other players.
+    Frags,
+    /// Ratio between frags and deaths.
+    KillDeathRatio,
+}
+
+impl RankingKey {
+    /// Compares two entries by this key, such that the entry ranking first is
+    /// the lesser one.
+    fn compare(self, left: &RankEntry, right: &RankEntry) -> Ordering {
+        match self {
+            Self::Score => right.score.cmp(&left.score),
+            Self::Frags => right.frags.cmp(&left.frags),
+            Self::KillDeathRatio => {
+                right.kill_death_ratio.total_cmp(&left.kill_death_ratio)
+            },
+        }
+    }
+}
+
+/// A single player in a ranking.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct RankEntry {
+    /// Position of the player, shared with tied players.
+    pub position: Position,
+    /// The name (or identity) of the player.
+    pub player: PlayerName,
+    /// Score in terms of killing, discounting deaths by the "world".
+    pub score: KillCount,
+    /// Kills of other players.
+    pub frags: KillCount,
+    /// Times the player died.
+    pub deaths: KillCount,
+    /// Ratio between frags and deaths. If the player never died, this is the
+    /// frag count.
+    pub kill_death_ratio: f64,
+}
+
+/// Players sorted by position. When serialized, this is a list in ranking
+/// order.
+#[derive(Debug, Clone, PartialEq, Default, serde::Serialize)]
+#[serde(transparent)]
+pub struct Ranking {
+    /// Entries sorted by position, and by name among tied players.
+    pub entries: Vec<RankEntry>,
+}
+
+impl Ranking {
+    /// Default sort keys: score only.
+    pub const DEFAULT_KEYS: &'static [RankingKey] = &[RankingKey::Score];
+
+    /// Generates the ranking of the given players and their statistics,
+    /// sorting by the given keys: the first key is the primary one, and the
+    /// following keys break ties of the previous ones. Players equal in all
+    /// keys are tied.
+    pub fn generate<'stats, I>(players: I, keys: &[RankingKey]) -> Self
+    where
+        I: IntoIterator<Item = (&'stats PlayerName, &'stats PlayerStats)>,
+    {
+        let mut entries: Vec<_> = players
+            .into_iter()
+            .map(|(name, stats)| RankEntry {
+                position: 0,
+                player: name.clone(),
+                score: stats.score,
+                frags: stats.frags,
+                deaths: stats.deaths,
+                kill_death_ratio: if stats.deaths == 0 {
+                    stats.frags as f64
+                } else {
+                    stats.frags as f64 / stats.deaths as f64
+                },
+            })
+            .collect();
+
+        let compare = |left: &RankEntry, right: &RankEntry| {
+            keys.iter()
+                .map(|key| key.compare(left, right))
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or(Ordering::Equal)
+        };
+
+        entries.sort_by(|left, right| {
+            compare(left, right).then_with(|| left.player.cmp(&right.player))
+        });
+
+        for i in 0 .. entries.len() {
+            entries[i].position =
+                if i > 0 && compare(&entries[i - 1], &entries[i]).is_eq() {
+                    entries[i - 1].position
+                } else {
+                    i + 1
+                };
+        }
+
+        Self { entries }
+    }
+}
@@ -0,0 +1,80 @@
+use super::{Ranking, RankingKey};
+use crate::{game::PlayerName, report::stats::PlayerStats};
+use indexmap::IndexMap;
+
+fn stats() -> IndexMap<PlayerName, PlayerStats> {
+    [
+        ("Zeh", 3, 4, 2),
+        ("Mal", 5, 5, 1),
+        ("Isgalamido", 3, 3, 0),
+        ("Dono da Bola", -1, 0, 3),
+        ("Assasinu Credi", 3, 6, 3),
+    ]
+    .into_iter()
+    .map(|(name, score, frags, deaths)| {
+        (PlayerName::from(name), PlayerStats { score, frags, deaths })
+    })
+    .collect()
+}
+
+fn positions(ranking: &Ranking) -> Vec<(usize, &str)> {
+    ranking
+        .entries
+        .iter()
+        .map(|entry| (entry.position, entry.player.as_str()))
+        .collect()
+}
+
+#[test]
+fn rank_by_score_with_ties() {
+    let ranking = Ranking::generate(&stats(), &[RankingKey::Score]);
+    assert_eq!(
+        positions(&ranking),
+        vec![
+            (1, "Mal"),
+            (2, "Assasinu Credi"),
+            (2, "Isgalamido"),
+            (2, "Zeh"),
+            (5, "Dono da Bola"),
+        ]
+    );
+}
+
+#[test]
+fn rank_by_score_then_frags() {
+    let ranking =
+        Ranking::generate(&stats(), &[RankingKey::Score, RankingKey::Frags]);
+    assert_eq!(
+        positions(&ranking),
+        vec![
+            (1, "Mal"),
+            (2, "Assasinu Credi"),
+            (3, "Zeh"),
+            (4, "Isgalamido"),
+            (5, "Dono da Bola"),
+        ]
+    );
+}
+
+#[test]
+fn rank_by_kill_death_ratio() {
+    let ranking = Ranking::generate(&stats(), &[RankingKey::KillDeathRatio]);
+    assert_eq!(
+        positions(&ranking),
+        vec![
+            (1, "Mal"),
+            (2, "Isgalamido"),
+            (3, "Assasinu Credi"),
+            (3, "Zeh"),
+            (5, "Dono da Bola"),
+        ]
+    );
+    assert_eq!(ranking.entries[1].kill_death_ratio, 3.0);
+    assert_eq!(ranking.entries[2].kill_death_ratio, 2.0);
+}
+
+#[test]
+fn rank_without_keys_ties_everyone() {
+    let ranking = Ranking::generate(&stats(), &[]);
+    assert!(ranking.entries.iter().all(|entry| entry.position == 1));
+}
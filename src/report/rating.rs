@@ -0,0 +1,390 @@
+//! This module exposes skill ratings (Elo or Glicko-2) of players, updated
+//! game by game in log order.
+//!
+//! In team games, each team competes as a single agent whose rating is the
+//! average of its players' ratings, and every player of a team receives the
+//! team's rating change (Elo) or is rated against the opposing teams
+//! (Glicko-2). In other games, every pair of players is a match decided by
+//! their finishing positions.
+
+use super::{totals::IdentityKey, GameName, GameReport};
+use crate::game::{PlayerName, Team};
+use indexmap::IndexMap;
+use std::{cmp::Ordering, f64::consts::PI};
+
+#[cfg(test)]
+mod test;
+
+/// A rating value, such as `1500.0`.
+pub type RatingValue = f64;
+
+/// Configuration of the Elo rating system.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EloConfig {
+    /// Rating of a player in their first game.
+    pub initial_rating: RatingValue,
+    /// Maximum rating change in a single game.
+    pub k_factor: f64,
+}
+
+impl Default for EloConfig {
+    fn default() -> Self {
+        Self { initial_rating: 1500.0, k_factor: 32.0 }
+    }
+}
+
+/// Configuration of the Glicko-2 rating system.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Glicko2Config {
+    /// Rating of a player in their first game.
+    pub initial_rating: RatingValue,
+    /// Rating deviation of a player in their first game.
+    pub initial_deviation: RatingValue,
+    /// Rating volatility of a player in their first game.
+    pub initial_volatility: f64,
+    /// System constant constraining the change in volatility over time.
+    pub tau: f64,
+}
+
+impl Default for Glicko2Config {
+    fn default() -> Self {
+        Self {
+            initial_rating: 1500.0,
+            initial_deviation: 350.0,
+            initial_volatility: 0.06,
+            tau: 0.5,
+        }
+    }
+}
+
+/// A rating system along with its configuration.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RatingSystem {
+    /// The Elo rating system.
+    Elo(EloConfig),
+    /// The Glicko-2 rating system. Each game is a rating period, in which only
+    /// the players of the game are updated.
+    Glicko2(Glicko2Config),
+}
+
+impl RatingSystem {
+    /// Rating of a player in their first game.
+    fn initial_rating(&self) -> Rating {
+        match self {
+            Self::Elo(config) => Rating {
+                rating: config.initial_rating,
+                deviation: None,
+                volatility: None,
+            },
+            Self::Glicko2(config) => Rating {
+                rating: config.initial_rating,
+                deviation: Some(config.initial_deviation),
+                volatility: Some(config.initial_volatility),
+            },
+        }
+    }
+}
+
+/// The rating of a player at some point.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize)]
+pub struct Rating {
+    /// The rating itself.
+    pub rating: RatingValue,
+    /// Rating deviation, only in Glicko-2.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deviation: Option<RatingValue>,
+    /// Rating volatility, only in Glicko-2.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub volatility: Option<f64>,
+}
+
+/// The rating of a player right after a game.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct RatingPoint {
+    /// Name of the game in the log report.
+    pub game: GameName,
+    /// The player's rating after the game.
+    #[serde(flatten)]
+    pub rating: Rating,
+}
+
+/// Current rating of a player and its history.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct PlayerRating {
+    /// Rating after the last game the player was in.
+    pub current: Rating,
+    /// Rating after each game the player was in, in log order.
+    pub history: Vec<RatingPoint>,
+}
+
+/// Ratings of every player, indexed by player identity.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(transparent)]
+pub struct Ratings {
+    /// The rating system and its configuration.
+    #[serde(skip)]
+    system: RatingSystem,
+    /// How players are identified across games.
+    #[serde(skip)]
+    identity: IdentityKey,
+    /// Ratings indexed by player identity, in order of first appearance.
+    pub players: IndexMap<PlayerName, PlayerRating>,
+}
+
+/// A competitor in a game: a single player, or a team of players.
+#[derive(Debug, Clone)]
+struct Competitor {
+    /// Identities of the players competing together.
+    players: Vec<PlayerName>,
+    /// Rating of the competitor before the game.
+    rating: Rating,
+    /// Value deciding the outcome of matches, lower is better.
+    place: i64,
+}
+
+impl Ratings {
+    /// Creates empty ratings using the given system, identifying players with
+    /// the given key.
+    pub fn new(system: RatingSystem, identity: IdentityKey) -> Self {
+        Self { system, identity, players: IndexMap::new() }
+    }
+
+    /// The rating system and its configuration.
+    pub fn system(&self) -> &RatingSystem {
+        &self.system
+    }
+
+    /// Updates the ratings with the results of a game. Games must be given in
+    /// log order. Spectators and games with less than two competitors are
+    /// ignored.
+    pub fn add_game(&mut self, game_name: &str, report: &GameReport) {
+        let competitors = self.competitors(report);
+        if competitors.len() < 2 {
+            return;
+        }
+
+        let mut updates = Vec::new();
+        for (i, competitor) in competitors.iter().enumerate() {
+            let opponents: Vec<_> = competitors
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| i != j)
+                .map(|(_, opponent)| {
+                    let outcome = match competitor.place.cmp(&opponent.place) {
+                        Ordering::Less => 1.0,
+                        Ordering::Equal => 0.5,
+                        Ordering::Greater => 0.0,
+                    };
+                    (opponent.rating, outcome)
+                })
+                .collect();
+            for player in &competitor.players {
+                let current = self.current_rating(player);
+                let rating = match self.system {
+                    RatingSystem::Elo(config) => {
+                        let delta =
+                            elo_delta(config, competitor.rating, &opponents);
+                        Rating { rating: current.rating + delta, ..current }
+                    },
+                    RatingSystem::Glicko2(config) => {
+                        glicko2_update(config, current, &opponents)
+                    },
+                };
+                updates.push((player.clone(), rating));
+            }
+        }
+
+        for (player, rating) in updates {
+            let player_rating =
+                self.players.entry(player).or_insert_with(|| PlayerRating {
+                    current: rating,
+                    history: Vec::new(),
+                });
+            player_rating.current = rating;
+            player_rating
+                .history
+                .push(RatingPoint { game: GameName::from(game_name), rating });
+        }
+    }
+
+    /// Splits the players of a game into competitors. Players not in any
+    /// competitor, such as players without a team in team games, are left
+    /// out.
+    fn competitors(&self, report: &GameReport) -> Vec<Competitor> {
+        let mut seen = IndexMap::<PlayerName, Team>::new();
+        let mut places = IndexMap::<PlayerName, i64>::new();
+        for entry in &report.ranking.entries {
+            let team = report.teams.get(&entry.player).copied();
+            if team == Some(Team::Spectator) {
+                continue;
+            }
+            let identity = self.identity.identify(&entry.player);
+            if seen.contains_key(&identity) {
+                continue;
+            }
+            seen.insert(identity.clone(), team.unwrap_or_default());
+            places.insert(identity, entry.position as i64);
+        }
+
+        if report.game_type.is_team_game() {
+            report
+                .team_scores
+                .iter()
+                .filter_map(|(&team, &score)| {
+                    let players: Vec<_> = seen
+                        .iter()
+                        .filter(|&(_, &player_team)| player_team == team)
+                        .map(|(player, _)| player.clone())
+                        .collect();
+                    let rating = self.average_rating(&players)?;
+                    Some(Competitor { players, rating, place: -score })
+                })
+                .collect()
+        } else {
+            places
+                .into_iter()
+                .map(|(player, place)| Competitor {
+                    rating: self.current_rating(&player),
+                    players: vec![player],
+                    place,
+                })
+                .collect()
+        }
+    }
+
+    /// Current rating of a player, or the initial rating if they have not
+    /// been rated yet.
+    fn current_rating(&self, player: &str) -> Rating {
+        self.players
+            .get(player)
+            .map_or_else(|| self.system.initial_rating(), |rated| rated.current)
+    }
+
+    /// Average rating of a team, `None` if the team has no players. Deviations
+    /// are averaged by their squares.
+    fn average_rating(&self, players: &[PlayerName]) -> Option<Rating> {
+        if players.is_empty() {
+            return None;
+        }
+        let count = players.len() as f64;
+        let mut average = self.system.initial_rating();
+        average.rating = 0.0;
+        average.deviation = average.deviation.map(|_| 0.0);
+        for player in players {
+            let rating = self.current_rating(player);
+            average.rating += rating.rating / count;
+            if let (Some(sum), Some(deviation)) =
+                (&mut average.deviation, rating.deviation)
+            {
+                *sum += deviation * deviation / count;
+            }
+        }
+        average.deviation = average.deviation.map(f64::sqrt);
+        Some(average)
+    }
+}
+
+/// Elo rating change against many opponents, given their ratings and the
+/// outcome against each of them (`1` = win, `0.5` = draw, `0` = loss). The
+/// K-factor is split among the opponents, so a single game never changes a
+/// rating by more than the K-factor.
+fn elo_delta(
+    config: EloConfig,
+    rating: Rating,
+    opponents: &[(Rating, f64)],
+) -> RatingValue {
+    let k_factor = config.k_factor / opponents.len().max(1) as f64;
+    opponents
+        .iter()
+        .map(|(opponent, outcome)| {
+            let exponent = (opponent.rating - rating.rating) / 400.0;
+            let expected = 1.0 / (1.0 + 10f64.powf(exponent));
+            k_factor * (outcome - expected)
+        })
+        .sum()
+}
+
+/// Glicko-2 scale conversion factor.
+const GLICKO2_SCALE: f64 = 173.7178;
+
+/// Convergence tolerance of the Glicko-2 volatility iteration.
+const GLICKO2_EPSILON: f64 = 0.000001;
+
+/// Glicko-2 update of a rating period with the given opponents and outcomes,
+/// following Glickman's "Example of the Glicko-2 system".
+fn glicko2_update(
+    config: Glicko2Config,
+    rating: Rating,
+    opponents: &[(Rating, f64)],
+) -> Rating {
+    let mu = (rating.rating - config.initial_rating) / GLICKO2_SCALE;
+    let phi =
+        rating.deviation.unwrap_or(config.initial_deviation) / GLICKO2_SCALE;
+    let sigma = rating.volatility.unwrap_or(config.initial_volatility);
+
+    let g = |phi: f64| 1.0 / (1.0 + 3.0 * phi * phi / (PI * PI)).sqrt();
+
+    let mut inverse_variance = 0.0;
+    let mut improvement = 0.0;
+    for &(opponent, outcome) in opponents {
+        let opponent_mu =
+            (opponent.rating - config.initial_rating) / GLICKO2_SCALE;
+        let opponent_phi =
+            opponent.deviation.unwrap_or(config.initial_deviation)
+                / GLICKO2_SCALE;
+        let g_phi = g(opponent_phi);
+        let expected = 1.0 / (1.0 + (-g_phi * (mu - opponent_mu)).exp());
+        inverse_variance += g_phi * g_phi * expected * (1.0 - expected);
+        improvement += g_phi * (outcome - expected);
+    }
+    if inverse_variance == 0.0 {
+        return rating;
+    }
+    let variance = 1.0 / inverse_variance;
+    let delta = variance * improvement;
+
+    let a = (sigma * sigma).ln();
+    let tau = config.tau;
+    let f = |x: f64| {
+        let exp_x = x.exp();
+        let denominator = phi * phi + variance + exp_x;
+        exp_x * (delta * delta - phi * phi - variance - exp_x)
+            / (2.0 * denominator * denominator)
+            - (x - a) / (tau * tau)
+    };
+    let mut lower = a;
+    let mut upper = if delta * delta > phi * phi + variance {
+        (delta * delta - phi * phi - variance).ln()
+    } else {
+        let mut k = 1.0;
+        while f(a - k * tau) < 0.0 {
+            k += 1.0;
+        }
+        a - k * tau
+    };
+    let mut f_lower = f(lower);
+    let mut f_upper = f(upper);
+    while (upper - lower).abs() > GLICKO2_EPSILON {
+        let middle = lower + (lower - upper) * f_lower / (f_upper - f_lower);
+        let f_middle = f(middle);
+        if f_middle * f_upper <= 0.0 {
+            lower = upper;
+            f_lower = f_upper;
+        } else {
+            f_lower /= 2.0;
+        }
+        upper = middle;
+        f_upper = f_middle;
+    }
+    let new_sigma = (lower / 2.0).exp();
+
+    let pre_phi = (phi * phi + new_sigma * new_sigma).sqrt();
+    let new_phi = 1.0 / (1.0 / (pre_phi * pre_phi) + inverse_variance).sqrt();
+    let new_mu = mu + new_phi * new_phi * improvement;
+
+    Rating {
+        rating: new_mu * GLICKO2_SCALE + config.initial_rating,
+        deviation: Some(new_phi * GLICKO2_SCALE),
+        volatility: Some(new_sigma),
+    }
+}
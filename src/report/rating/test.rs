@@ -0,0 +1,155 @@
+use super::{
+    glicko2_update,
+    EloConfig,
+    Glicko2Config,
+    Rating,
+    RatingSystem,
+    Ratings,
+};
+use crate::{
+    game::{Game, GameType, Kill, Killer, MeansOfDeath, PlayerName, Team},
+    report::{totals::IdentityKey, GameReport},
+};
+use std::collections::HashMap;
+
+fn kill(killer: u32, target: u32) -> Kill {
+    Kill {
+        killer: Killer::Player(killer),
+        target,
+        means: MeansOfDeath::from("MOD_RAILGUN"),
+        time: 0,
+    }
+}
+
+fn ffa_game() -> Game {
+    Game {
+        players: HashMap::from([
+            (2, PlayerName::from("Zeh")),
+            (3, PlayerName::from("Mal")),
+            (4, PlayerName::from("Isgalamido")),
+        ]),
+        kills: vec![kill(2, 3), kill(2, 4), kill(3, 4)],
+        ..Game::default()
+    }
+}
+
+fn team_game() -> Game {
+    Game {
+        game_type: GameType::TeamDeathmatch,
+        players: HashMap::from([
+            (2, PlayerName::from("Zeh")),
+            (3, PlayerName::from("Mal")),
+            (4, PlayerName::from("Isgalamido")),
+            (5, PlayerName::from("Oootsimo")),
+        ]),
+        teams: HashMap::from([
+            (2, Team::Red),
+            (3, Team::Red),
+            (4, Team::Blue),
+            (5, Team::Spectator),
+        ]),
+        kills: vec![kill(4, 2), kill(4, 3), kill(2, 4)],
+        ..Game::default()
+    }
+}
+
+fn elo_ratings() -> Ratings {
+    Ratings::new(RatingSystem::Elo(EloConfig::default()), IdentityKey::Name)
+}
+
+#[test]
+fn elo_free_for_all() {
+    let mut ratings = elo_ratings();
+    let report = GameReport::generate(&ffa_game()).unwrap();
+    ratings.add_game("game_1", &report);
+
+    let zeh = ratings.players["Zeh"].current.rating;
+    let mal = ratings.players["Mal"].current.rating;
+    let isgalamido = ratings.players["Isgalamido"].current.rating;
+    assert!((zeh - 1516.0).abs() < 1e-9);
+    assert!((mal - 1500.0).abs() < 1e-9);
+    assert!((isgalamido - 1484.0).abs() < 1e-9);
+    assert_eq!(ratings.players["Zeh"].history.len(), 1);
+    assert_eq!(ratings.players["Zeh"].history[0].game, "game_1");
+    assert_eq!(ratings.players["Zeh"].current.deviation, None);
+}
+
+#[test]
+fn elo_team_game() {
+    let mut ratings = elo_ratings();
+    let report = GameReport::generate(&team_game()).unwrap();
+    ratings.add_game("game_1", &report);
+
+    assert!((ratings.players["Zeh"].current.rating - 1484.0).abs() < 1e-9);
+    assert!((ratings.players["Mal"].current.rating - 1484.0).abs() < 1e-9);
+    let isgalamido = ratings.players["Isgalamido"].current.rating;
+    assert!((isgalamido - 1516.0).abs() < 1e-9);
+    assert!(!ratings.players.contains_key("Oootsimo"));
+}
+
+#[test]
+fn elo_team_game_leaves_out_players_without_team() {
+    let mut ratings = elo_ratings();
+    let mut game = team_game();
+    game.players.insert(6, PlayerName::from("Dono da Bola"));
+    game.teams.insert(6, Team::Free);
+    game.players.insert(7, PlayerName::from("Assasinu Credi"));
+    let report = GameReport::generate(&game).unwrap();
+    ratings.add_game("game_1", &report);
+
+    assert_eq!(ratings.players.len(), 3);
+    assert!(!ratings.players.contains_key("Dono da Bola"));
+    assert!(!ratings.players.contains_key("Assasinu Credi"));
+    assert!((ratings.players["Zeh"].current.rating - 1484.0).abs() < 1e-9);
+}
+
+#[test]
+fn elo_history_follows_games() {
+    let mut ratings = elo_ratings();
+    let report = GameReport::generate(&ffa_game()).unwrap();
+    ratings.add_game("game_1", &report);
+    ratings.add_game("game_2", &report);
+
+    let history = &ratings.players["Zeh"].history;
+    assert_eq!(history.len(), 2);
+    assert!(history[1].rating.rating > history[0].rating.rating);
+    assert!(history[1].rating.rating - history[0].rating.rating < 16.0);
+}
+
+#[test]
+fn glicko2_reference_example() {
+    let config = Glicko2Config::default();
+    let rating = |rating, deviation| Rating {
+        rating,
+        deviation: Some(deviation),
+        volatility: Some(0.06),
+    };
+    let updated = glicko2_update(
+        config,
+        rating(1500.0, 200.0),
+        &[
+            (rating(1400.0, 30.0), 1.0),
+            (rating(1550.0, 100.0), 0.0),
+            (rating(1700.0, 300.0), 0.0),
+        ],
+    );
+    assert!((updated.rating - 1464.06).abs() < 0.01);
+    assert!((updated.deviation.unwrap() - 151.52).abs() < 0.01);
+    assert!((updated.volatility.unwrap() - 0.05999).abs() < 0.00001);
+}
+
+#[test]
+fn glicko2_free_for_all() {
+    let mut ratings = Ratings::new(
+        RatingSystem::Glicko2(Glicko2Config::default()),
+        IdentityKey::Name,
+    );
+    let report = GameReport::generate(&ffa_game()).unwrap();
+    ratings.add_game("game_1", &report);
+
+    let zeh = ratings.players["Zeh"].current;
+    let isgalamido = ratings.players["Isgalamido"].current;
+    assert!(zeh.rating > 1500.0);
+    assert!(isgalamido.rating < 1500.0);
+    assert!(zeh.deviation.unwrap() < 350.0);
+}
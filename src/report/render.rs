@@ -0,0 +1,290 @@
+//! This module exposes rendering of reports into output formats, such as JSON
+//! or Markdown tables. Each format is behind its own cargo feature.
+
+use super::{
+    ranking::Ranking,
+    totals::PlayerTotals,
+    GameName,
+    GameReport,
+    LogReport,
+};
+use crate::error::Result;
+use indexmap::IndexMap;
+use std::io::Write;
+#[cfg(feature = "csv")]
+use super::ranking::Position;
+#[cfg(feature = "csv")]
+use crate::game::{GameType, KillCount, Team};
+
+#[cfg(feature = "scoreboard")]
+pub mod scoreboard;
+
+#[cfg(test)]
+mod test;
+
+/// Name of a game report rendered on its own.
+const SINGLE_GAME_NAME: &str = "game";
+
+/// A report that can be rendered in any format: the whole log report, a single
+/// game report, or a dictionary of named game reports.
+pub trait Renderable: serde::Serialize {
+    /// Game reports in this report, along with their names, in order.
+    fn game_reports(&self) -> Vec<(&str, &GameReport)>;
+
+    /// Players sorted by statistics aggregated across all games, if this
+    /// report spans the whole log.
+    fn overall_ranking(&self) -> Option<&Ranking> {
+        None
+    }
+
+    /// Statistics of each player aggregated across all games, if this report
+    /// spans the whole log.
+    fn player_totals(&self) -> Option<&PlayerTotals> {
+        None
+    }
+}
+
+impl Renderable for LogReport {
+    fn game_reports(&self) -> Vec<(&str, &GameReport)> {
+        self.games.game_reports()
+    }
+
+    fn overall_ranking(&self) -> Option<&Ranking> {
+        Some(&self.aggregates.ranking)
+    }
+
+    fn player_totals(&self) -> Option<&PlayerTotals> {
+        Some(&self.aggregates.player_totals)
+    }
+}
+
+impl Renderable for GameReport {
+    fn game_reports(&self) -> Vec<(&str, &GameReport)> {
+        vec![(SINGLE_GAME_NAME, self)]
+    }
+}
+
+impl Renderable for IndexMap<GameName, GameReport> {
+    fn game_reports(&self) -> Vec<(&str, &GameReport)> {
+        self.iter().map(|(name, report)| (name.as_str(), report)).collect()
+    }
+}
+
+/// Output format of a report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Format {
+    /// Indented JSON, the whole report as a single object.
+    #[cfg(feature = "json")]
+    JsonPretty,
+    /// JSON without whitespace, the whole report as a single object.
+    #[cfg(feature = "json")]
+    JsonCompact,
+    /// Newline-delimited JSON: one object per game, mapping the game name to
+    /// its report. Sections aggregated across games are left out.
+    #[cfg(feature = "ndjson")]
+    Ndjson,
+    /// YAML, the whole report as a single document.
+    #[cfg(feature = "yaml")]
+    Yaml,
+    /// CSV with one record per player per game, flattening the ranking
+    /// entries of each game.
+    #[cfg(feature = "csv")]
+    Csv,
+    /// Markdown, with a section per game holding ranking and MOD tables, and
+    /// the overall ranking of the log, if any.
+    #[cfg(feature = "markdown")]
+    Markdown,
+    /// Scoreboards aligned for terminals, one per game, with the map,
+    /// duration, ranking and top weapons of the game, and the overall ranking
+    /// of the log, if any. If colored, ANSI escape codes render the Quake
+    /// color codes of player names.
+    #[cfg(feature = "scoreboard")]
+    Scoreboard {
+        /// Whether ANSI escape codes are written.
+        color: bool,
+    },
+    /// A self-contained HTML page, with an index of games, sortable
+    /// scoreboards, weapon charts and player pages.
+    #[cfg(feature = "html")]
+    Html,
+}
+
+impl Format {
+    /// Renders the given report in this format into the given writer.
+    pub fn render<T, W>(self, report: &T, mut writer: W) -> Result<()>
+    where
+        T: Renderable + ?Sized,
+        W: Write,
+    {
+        match self {
+            #[cfg(feature = "json")]
+            Self::JsonPretty => {
+                serde_json::to_writer_pretty(&mut writer, report)?;
+                writeln!(writer)?;
+            },
+            #[cfg(feature = "json")]
+            Self::JsonCompact => {
+                serde_json::to_writer(&mut writer, report)?;
+                writeln!(writer)?;
+            },
+            #[cfg(feature = "ndjson")]
+            Self::Ndjson => {
+                for (name, game_report) in report.game_reports() {
+                    let entry = IndexMap::from([(name, game_report)]);
+                    serde_json::to_writer(&mut writer, &entry)?;
+                    writeln!(writer)?;
+                }
+            },
+            #[cfg(feature = "yaml")]
+            Self::Yaml => serde_yaml::to_writer(&mut writer, report)?,
+            #[cfg(feature = "csv")]
+            Self::Csv => render_csv(report, &mut writer)?,
+            #[cfg(feature = "markdown")]
+            Self::Markdown => render_markdown(report, &mut writer)?,
+            #[cfg(feature = "scoreboard")]
+            Self::Scoreboard { color } => {
+                scoreboard::render_scoreboard(report, &mut writer, color)?
+            },
+            #[cfg(feature = "html")]
+            Self::Html => super::html::render_html(report, &mut writer)?,
+        }
+        Ok(())
+    }
+}
+
+/// A CSV record: a player in the ranking of a game.
+#[cfg(feature = "csv")]
+#[derive(Debug, Clone, serde::Serialize)]
+struct CsvRecord<'report> {
+    /// Name of the game.
+    game: &'report str,
+    /// The type of the game.
+    game_type: GameType,
+    /// Position of the player in the game.
+    position: Position,
+    /// Name of the player.
+    player: &'report str,
+    /// Team the player was last in, if any.
+    team: Option<Team>,
+    /// Score of the player.
+    score: KillCount,
+    /// Kills of other players.
+    frags: KillCount,
+    /// Times the player died.
+    deaths: KillCount,
+    /// Ratio between frags and deaths.
+    kill_death_ratio: f64,
+}
+
+/// Renders the report as CSV, one record per player per game.
+#[cfg(feature = "csv")]
+fn render_csv<T, W>(report: &T, writer: W) -> Result<()>
+where
+    T: Renderable + ?Sized,
+    W: Write,
+{
+    let mut csv_writer = csv::Writer::from_writer(writer);
+    for (game, game_report) in report.game_reports() {
+        for entry in &game_report.ranking.entries {
+            csv_writer.serialize(CsvRecord {
+                game,
+                game_type: game_report.game_type,
+                position: entry.position,
+                player: &entry.player,
+                team: game_report.teams.get(&entry.player).copied(),
+                score: entry.score,
+                frags: entry.frags,
+                deaths: entry.deaths,
+                kill_death_ratio: entry.kill_death_ratio,
+            })?;
+        }
+    }
+    csv_writer.flush()?;
+    Ok(())
+}
+
+/// Renders the report as Markdown tables.
+#[cfg(feature = "markdown")]
+fn render_markdown<T, W>(report: &T, mut writer: W) -> Result<()>
+where
+    T: Renderable + ?Sized,
+    W: Write,
+{
+    let game_reports = report.game_reports();
+    for (i, (game, game_report)) in game_reports.into_iter().enumerate() {
+        if i > 0 {
+            writeln!(writer)?;
+        }
+        writeln!(writer, "## {}", game)?;
+        writeln!(writer)?;
+        writeln!(
+            writer,
+            "Game type: {}. Total kills: {}.",
+            escape_markdown(game_report.game_type.name()),
+            game_report.total_kills,
+        )?;
+        writeln!(writer)?;
+        render_markdown_ranking(&game_report.ranking, &mut writer)?;
+
+        let mut means = game_report
+            .kills_by_means
+            .iter()
+            .filter(|(_, &kills)| kills != 0)
+            .peekable();
+        if means.peek().is_some() {
+            writeln!(writer)?;
+            writeln!(writer, "| Means of death | Kills |")?;
+            writeln!(writer, "| --- | ---: |")?;
+            for (means, kills) in means {
+                let means = escape_markdown(means.name());
+                writeln!(writer, "| {} | {} |", means, kills)?;
+            }
+        }
+    }
+
+    if let Some(ranking) = report.overall_ranking() {
+        writeln!(writer)?;
+        writeln!(writer, "## Overall ranking")?;
+        writeln!(writer)?;
+        render_markdown_ranking(ranking, &mut writer)?;
+    }
+    Ok(())
+}
+
+/// Renders a ranking as a Markdown table.
+#[cfg(feature = "markdown")]
+fn render_markdown_ranking<W>(ranking: &Ranking, mut writer: W) -> Result<()>
+where
+    W: Write,
+{
+    writeln!(writer, "| Position | Player | Score | Frags | Deaths | K/D |")?;
+    writeln!(writer, "| ---: | --- | ---: | ---: | ---: | ---: |")?;
+    for entry in &ranking.entries {
+        writeln!(
+            writer,
+            "| {} | {} | {} | {} | {} | {:.2} |",
+            entry.position,
+            escape_markdown(&entry.player),
+            entry.score,
+            entry.frags,
+            entry.deaths,
+            entry.kill_death_ratio,
+        )?;
+    }
+    Ok(())
+}
+
+/// Escapes characters of a name that would break Markdown tables or add
+/// formatting.
+#[cfg(feature = "markdown")]
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for character in text.chars() {
+        if matches!(character, '|' | '\\' | '*' | '_' | '`' | '#' | '[' | ']')
+        {
+            escaped.push('\\');
+        }
+        escaped.push(character);
+    }
+    escaped
+}
@@ -0,0 +1,277 @@
+//! This module exposes rendering of reports as scoreboards: plain text tables
+//! aligned for terminals, optionally colored with ANSI escape codes, in which
+//! Quake color codes of player names are rendered as well.
+
+use super::Renderable;
+use crate::{
+    error::Result,
+    game::{
+        color_pieces,
+        format_timestamp,
+        ColorPiece,
+        PlayerName,
+        Team,
+        Weapon,
+    },
+    report::{ranking::Ranking, GameReport},
+};
+use indexmap::IndexMap;
+use std::io::Write;
+
+#[cfg(test)]
+mod test;
+
+/// How many weapons with the most kills are shown for each game.
+pub const TOP_WEAPONS: usize = 3;
+
+/// ANSI escape code resetting colors and styles.
+const ANSI_RESET: &str = "\x1b[0m";
+
+/// ANSI escape code of bold text.
+const ANSI_BOLD: &str = "\x1b[1m";
+
+/// ANSI foreground colors of the eight Quake colors, in Quake's order: black,
+/// red, green, yellow, blue, cyan, magenta and white.
+const ANSI_COLORS: [u8; 8] = [30, 31, 32, 33, 34, 36, 35, 37];
+
+/// Converts the Quake color codes of the given text into ANSI escape codes,
+/// e.g. `^1Isga^7lamido` becomes `Isga` in red and `lamido` in white. Colors
+/// are reset at the end of the text if any color code was found.
+pub fn quake_to_ansi(text: &str) -> String {
+    let mut converted = String::with_capacity(text.len());
+    let mut colored = false;
+    for piece in color_pieces(text) {
+        match piece {
+            ColorPiece::Color(index) => {
+                converted.push_str(&format!("\x1b[{}m", ANSI_COLORS[index]));
+                colored = true;
+            },
+            ColorPiece::Char(ch) => converted.push(ch),
+        }
+    }
+    if colored {
+        converted.push_str(ANSI_RESET);
+    }
+    converted
+}
+
+/// Width of the given text as displayed, i.e. without color codes.
+fn display_width(text: &str) -> usize {
+    color_pieces(text)
+        .filter(|piece| matches!(piece, ColorPiece::Char(_)))
+        .count()
+}
+
+/// Name of a team as displayed, in the color of the team.
+fn team_name(team: &Team) -> &'static str {
+    match team {
+        Team::Free => "Free",
+        Team::Red => "^1Red",
+        Team::Blue => "^4Blue",
+        Team::Spectator => "Spectator",
+    }
+}
+
+/// Alignment of a table column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Align {
+    /// Text aligned to the left, padded on the right.
+    Left,
+    /// Text aligned to the right, such as numbers, padded on the left.
+    Right,
+}
+
+/// Renderer of scoreboards into a writer.
+struct Scoreboard<W> {
+    /// Where the scoreboard is written to.
+    writer: W,
+    /// Whether ANSI escape codes are written.
+    color: bool,
+}
+
+impl<W> Scoreboard<W>
+where
+    W: Write,
+{
+    /// Writes a section title, bold if colored.
+    fn write_title(&mut self, title: &str) -> Result<()> {
+        if self.color {
+            writeln!(self.writer, "{}{}{}", ANSI_BOLD, title, ANSI_RESET)?;
+        } else {
+            writeln!(self.writer, "{}", title)?;
+        }
+        Ok(())
+    }
+
+    /// Writes a cell of text with Quake color codes, padded to the given
+    /// width. Color codes are rendered if colored and left out otherwise.
+    fn write_cell(
+        &mut self,
+        text: &str,
+        width: usize,
+        align: Align,
+    ) -> Result<()> {
+        let padding = " ".repeat(width.saturating_sub(display_width(text)));
+        let displayed: String = if self.color {
+            quake_to_ansi(text)
+        } else {
+            color_pieces(text)
+                .filter_map(|piece| match piece {
+                    ColorPiece::Char(ch) => Some(ch),
+                    ColorPiece::Color(_) => None,
+                })
+                .collect()
+        };
+        match align {
+            Align::Left => write!(self.writer, "{}{}", displayed, padding)?,
+            Align::Right => write!(self.writer, "{}{}", padding, displayed)?,
+        }
+        Ok(())
+    }
+
+    /// Writes a table with the given columns, as pairs of header and
+    /// alignment, and rows of cells. Columns are separated by two spaces and
+    /// headers are bold if colored.
+    fn write_table(
+        &mut self,
+        columns: &[(&str, Align)],
+        rows: &[Vec<String>],
+    ) -> Result<()> {
+        let widths: Vec<usize> = columns
+            .iter()
+            .enumerate()
+            .map(|(i, (header, _))| {
+                rows.iter()
+                    .map(|row| display_width(&row[i]))
+                    .fold(header.len(), usize::max)
+            })
+            .collect();
+
+        if self.color {
+            write!(self.writer, "{}", ANSI_BOLD)?;
+        }
+        for (i, &(header, align)) in columns.iter().enumerate() {
+            if i > 0 {
+                write!(self.writer, "  ")?;
+            }
+            self.write_cell(header, widths[i], align)?;
+        }
+        if self.color {
+            write!(self.writer, "{}", ANSI_RESET)?;
+        }
+        writeln!(self.writer)?;
+
+        for row in rows {
+            for (i, &(_, align)) in columns.iter().enumerate() {
+                if i > 0 {
+                    write!(self.writer, "  ")?;
+                }
+                self.write_cell(&row[i], widths[i], align)?;
+            }
+            writeln!(self.writer)?;
+        }
+        Ok(())
+    }
+
+    /// Writes the scoreboard of a game.
+    fn write_game(&mut self, name: &str, report: &GameReport) -> Result<()> {
+        self.write_title(name)?;
+        writeln!(
+            self.writer,
+            "Map: {}  Type: {}  Duration: {}  Kills: {}",
+            report.map.as_deref().unwrap_or("unknown"),
+            report.game_type,
+            format_timestamp(report.duration),
+            report.total_kills,
+        )?;
+        writeln!(self.writer)?;
+
+        let teams = report.game_type.is_team_game().then_some(&report.teams);
+        self.write_ranking(&report.ranking, teams)?;
+
+        let mut weapons: Vec<(Weapon, _)> = report
+            .kills_by_weapon
+            .iter()
+            .filter(|&(&weapon, &kills)| weapon != Weapon::Other && kills > 0)
+            .map(|(&weapon, &kills)| (weapon, kills))
+            .collect();
+        weapons.sort_by(|(_, left), (_, right)| right.cmp(left));
+        if !weapons.is_empty() {
+            let top: Vec<String> = weapons
+                .iter()
+                .take(TOP_WEAPONS)
+                .map(|(weapon, kills)| format!("{} ({})", weapon, kills))
+                .collect();
+            writeln!(self.writer)?;
+            writeln!(self.writer, "Top weapons: {}", top.join(", "))?;
+        }
+        Ok(())
+    }
+
+    /// Writes a ranking table, with the team of each player, if given.
+    fn write_ranking(
+        &mut self,
+        ranking: &Ranking,
+        teams: Option<&IndexMap<PlayerName, Team>>,
+    ) -> Result<()> {
+        let mut columns = vec![("#", Align::Right), ("Player", Align::Left)];
+        if teams.is_some() {
+            columns.push(("Team", Align::Left));
+        }
+        columns.extend([
+            ("Score", Align::Right),
+            ("Frags", Align::Right),
+            ("Deaths", Align::Right),
+            ("K/D", Align::Right),
+        ]);
+        let rows: Vec<Vec<String>> = ranking
+            .entries
+            .iter()
+            .map(|entry| {
+                let mut row =
+                    vec![entry.position.to_string(), entry.player.clone()];
+                if let Some(teams) = teams {
+                    let team = teams.get(&entry.player);
+                    row.push(String::from(team.map_or("", team_name)));
+                }
+                row.extend([
+                    entry.score.to_string(),
+                    entry.frags.to_string(),
+                    entry.deaths.to_string(),
+                    format!("{:.2}", entry.kill_death_ratio),
+                ]);
+                row
+            })
+            .collect();
+        self.write_table(&columns, &rows)
+    }
+}
+
+/// Renders the report as scoreboards, one per game, followed by the overall
+/// ranking of the log, if any.
+pub(super) fn render_scoreboard<T, W>(
+    report: &T,
+    writer: W,
+    color: bool,
+) -> Result<()>
+where
+    T: Renderable + ?Sized,
+    W: Write,
+{
+    let mut scoreboard = Scoreboard { writer, color };
+    let game_reports = report.game_reports();
+    for (i, (name, game_report)) in game_reports.iter().enumerate() {
+        if i > 0 {
+            writeln!(scoreboard.writer)?;
+        }
+        scoreboard.write_game(name, game_report)?;
+    }
+    if let Some(ranking) = report.overall_ranking() {
+        if !game_reports.is_empty() {
+            writeln!(scoreboard.writer)?;
+        }
+        scoreboard.write_title("Overall ranking")?;
+        scoreboard.write_ranking(ranking, None)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,91 @@
+use super::{display_width, quake_to_ansi, render_scoreboard};
+use crate::{
+    game::{Game, GameType, Kill, Killer, MeansOfDeath, PlayerName, Team},
+    report::GameReport,
+};
+use std::collections::HashMap;
+
+fn team_game() -> Game {
+    Game {
+        game_type: GameType::TeamDeathmatch,
+        map: Some(String::from("q3dm17")),
+        start_time: 60,
+        end_time: Some(10 * 60 + 5),
+        players: HashMap::from([
+            (2, PlayerName::from("^1Isga^7lamido")),
+            (3, PlayerName::from("Zeh")),
+        ]),
+        teams: HashMap::from([(2, Team::Red), (3, Team::Blue)]),
+        kills: vec![
+            Kill {
+                killer: Killer::Player(2),
+                target: 3,
+                means: MeansOfDeath::from("MOD_RAILGUN"),
+                time: 2 * 60,
+            },
+            Kill {
+                killer: Killer::Player(2),
+                target: 3,
+                means: MeansOfDeath::from("MOD_ROCKET_SPLASH"),
+                time: 3 * 60,
+            },
+            Kill {
+                killer: Killer::Player(3),
+                target: 2,
+                means: MeansOfDeath::from("MOD_RAILGUN"),
+                time: 4 * 60,
+            },
+            Kill {
+                killer: Killer::World,
+                target: 3,
+                means: MeansOfDeath::from("MOD_FALLING"),
+                time: 5 * 60,
+            },
+        ],
+        ..Game::default()
+    }
+}
+
+fn render(report: &GameReport, color: bool) -> String {
+    let mut output = Vec::new();
+    render_scoreboard(report, &mut output, color).unwrap();
+    String::from_utf8(output).unwrap()
+}
+
+#[test]
+fn convert_quake_colors() {
+    assert_eq!(
+        quake_to_ansi("^1Isga^7lamido"),
+        "\x1b[31mIsga\x1b[37mlamido\x1b[0m",
+    );
+    assert_eq!(quake_to_ansi("^5Zeh^9!"), "\x1b[36mZeh\x1b[31m!\x1b[0m");
+    assert_eq!(quake_to_ansi("Zeh^^"), "Zeh^^");
+    assert_eq!(display_width("^1Isga^7lamido"), 10);
+}
+
+#[test]
+fn render_plain_scoreboard() {
+    let report = GameReport::generate(&team_game()).unwrap();
+    let expected = concat!(
+        "game\n",
+        "Map: q3dm17  Type: team_deathmatch  Duration: 9:05  Kills: 4\n",
+        "\n",
+        "#  Player      Team  Score  Frags  Deaths   K/D\n",
+        "1  Isgalamido  Red       2      2       1  2.00\n",
+        "2  Zeh         Blue      0      1       3  0.33\n",
+        "\n",
+        "Top weapons: railgun (2), rocket_launcher (1)\n",
+    );
+    assert_eq!(render(&report, false), expected);
+}
+
+#[test]
+fn render_colored_scoreboard() {
+    let report = GameReport::generate(&team_game()).unwrap();
+    let output = render(&report, true);
+    assert!(output.starts_with("\x1b[1mgame\x1b[0m\n"));
+    assert!(output.contains(
+        "1  \x1b[31mIsga\x1b[37mlamido\x1b[0m  \x1b[31mRed\x1b[0m ",
+    ));
+    assert!(output.contains("2  Zeh         \x1b[34mBlue\x1b[0m"));
+}
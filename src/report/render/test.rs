@@ -0,0 +1,152 @@
+use super::{Format, Renderable};
+use crate::{
+    game::{Game, Kill, Killer, MeansOfDeath, PlayerName, Team},
+    report::{GameReport, LogReport},
+};
+use std::collections::HashMap;
+
+fn game() -> Game {
+    Game {
+        players: HashMap::from([
+            (2, PlayerName::from("Dono da Bola")),
+            (3, PlayerName::from("Isgalamido")),
+            (4, PlayerName::from("Zeh|Pipe")),
+        ]),
+        teams: HashMap::from([(2, Team::Red), (3, Team::Blue)]),
+        kills: vec![
+            Kill {
+                killer: Killer::World,
+                target: 3,
+                means: MeansOfDeath::from("MOD_TRIGGER_HURT"),
+                time: 2 * 60,
+            },
+            Kill {
+                killer: Killer::Player(2),
+                target: 4,
+                means: MeansOfDeath::from("MOD_ROCKET"),
+                time: 2 * 60 + 11,
+            },
+        ],
+        ..Game::default()
+    }
+}
+
+fn log_report() -> LogReport {
+    let solo = Game {
+        players: HashMap::from([(2, PlayerName::from("Isgalamido"))]),
+        ..Game::default()
+    };
+    LogReport::generate([Ok(solo), Ok(game())]).unwrap()
+}
+
+fn render<T>(format: Format, report: &T) -> String
+where
+    T: Renderable + ?Sized,
+{
+    let mut output = Vec::new();
+    format.render(report, &mut output).unwrap();
+    String::from_utf8(output).unwrap()
+}
+
+#[test]
+fn game_reports() {
+    let report = log_report();
+    let names: Vec<_> =
+        report.game_reports().into_iter().map(|(name, _)| name).collect();
+    assert_eq!(names, ["game_1", "game_2"]);
+    assert!(report.overall_ranking().is_some());
+    assert!(report.player_totals().is_some());
+
+    let game_report = GameReport::generate(&game()).unwrap();
+    assert_eq!(game_report.game_reports(), [("game", &game_report)]);
+    assert!(game_report.overall_ranking().is_none());
+    assert!(game_report.player_totals().is_none());
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn render_json() {
+    let report = log_report();
+    let expected = serde_json::to_value(&report).unwrap();
+    for format in [Format::JsonPretty, Format::JsonCompact] {
+        let output = render(format, &report);
+        let actual: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(expected, actual);
+    }
+    assert_eq!(render(Format::JsonCompact, &report).lines().count(), 1);
+}
+
+#[cfg(feature = "ndjson")]
+#[test]
+fn render_ndjson() {
+    let report = log_report();
+    let output = render(Format::Ndjson, &report);
+    let lines: Vec<serde_json::Value> = output
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 2);
+    let expected = serde_json::to_value(&report.games["game_2"]).unwrap();
+    assert_eq!(lines[1]["game_2"], expected);
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn render_yaml() {
+    let report = log_report();
+    let output = render(Format::Yaml, &report);
+    let actual: serde_yaml::Value = serde_yaml::from_str(&output).unwrap();
+    assert_eq!(serde_yaml::to_value(&report).unwrap(), actual);
+}
+
+#[cfg(feature = "csv")]
+#[test]
+fn render_csv() {
+    let expected = concat!(
+        "game,game_type,position,player,team,score,frags,deaths,",
+        "kill_death_ratio\n",
+        "game_1,free_for_all,1,Isgalamido,,0,0,0,0.0\n",
+        "game_2,free_for_all,1,Dono da Bola,red,1,1,0,1.0\n",
+        "game_2,free_for_all,2,Zeh|Pipe,,0,0,1,0.0\n",
+        "game_2,free_for_all,3,Isgalamido,blue,-1,0,1,0.0\n",
+    );
+    assert_eq!(render(Format::Csv, &log_report()), expected);
+}
+
+#[cfg(feature = "markdown")]
+#[test]
+fn render_markdown() {
+    let expected = concat!(
+        "## game\n",
+        "\n",
+        "Game type: free\\_for\\_all. Total kills: 2.\n",
+        "\n",
+        "| Position | Player | Score | Frags | Deaths | K/D |\n",
+        "| ---: | --- | ---: | ---: | ---: | ---: |\n",
+        "| 1 | Dono da Bola | 1 | 1 | 0 | 1.00 |\n",
+        "| 2 | Zeh\\|Pipe | 0 | 0 | 1 | 0.00 |\n",
+        "| 3 | Isgalamido | -1 | 0 | 1 | 0.00 |\n",
+        "\n",
+        "| Means of death | Kills |\n",
+        "| --- | ---: |\n",
+        "| MOD\\_ROCKET | 1 |\n",
+        "| MOD\\_TRIGGER\\_HURT | 1 |\n",
+    );
+    let game_report = GameReport::generate(&game()).unwrap();
+    assert_eq!(render(Format::Markdown, &game_report), expected);
+
+    let output = render(Format::Markdown, &log_report());
+    assert!(output.starts_with("## game_1\n"));
+    assert!(output.contains("\n## game_2\n"));
+    assert!(output.contains("\n## Overall ranking\n"));
+}
+
+#[cfg(feature = "scoreboard")]
+#[test]
+fn render_scoreboard() {
+    let output = render(Format::Scoreboard { color: false }, &log_report());
+    assert!(output.starts_with("game_1\nMap: unknown  Type: free_for_all"));
+    assert!(output.contains("\n\ngame_2\n"));
+    assert!(output.contains("\n\nOverall ranking\n#  Player "));
+    assert!(!output.contains('\x1b'));
+}
@@ -0,0 +1,74 @@
+//! This module exposes per-player statistics of a single game, the basis of
+//! player-centric report sections.
+
+use crate::game::{Game, Kill, KillCount, Killer, PlayerId, PlayerName};
+use indexmap::IndexMap;
+
+/// Statistics of a single player in a single game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize)]
+pub struct PlayerStats {
+    /// Score in terms of killing, discounting `1` for each time the player
+    /// died because of the "world".
+    pub score: KillCount,
+    /// Kills of other players (suicides are not frags).
+    pub frags: KillCount,
+    /// Times the player died, by anyone or anything.
+    pub deaths: KillCount,
+}
+
+impl PlayerStats {
+    /// Generates the statistics of every player in the given game, indexed by
+    /// the last name they used.
+    pub fn generate_all(game: &Game) -> IndexMap<PlayerName, Self> {
+        let mut all: IndexMap<_, _> = game
+            .players
+            .values()
+            .cloned()
+            .map(|name| (name, Self::default()))
+            .collect();
+
+        for kill in &game.kills {
+            Self::add_kill(game, &mut all, kill);
+        }
+
+        all
+    }
+
+    /// Accounts a single kill of the given game into the statistics of every
+    /// player, indexed by the last name they used.
+    pub fn add_kill(
+        game: &Game,
+        all: &mut IndexMap<PlayerName, Self>,
+        kill: &Kill,
+    ) {
+        if let Killer::Player(killer_id) = kill.killer {
+            if let Some(stats) = find(game, all, killer_id, "killer") {
+                stats.score += 1;
+                if killer_id != kill.target {
+                    stats.frags += 1;
+                }
+            }
+        }
+        if let Some(stats) = find(game, all, kill.target, "target") {
+            stats.deaths += 1;
+            if kill.killer == Killer::World {
+                stats.score -= 1;
+            }
+        }
+    }
+}
+
+/// Finds the statistics of the player with the given ID, logging an error if
+/// the player is unknown. The role is used only in the log message.
+fn find<'all>(
+    game: &Game,
+    all: &'all mut IndexMap<PlayerName, PlayerStats>,
+    id: PlayerId,
+    role: &str,
+) -> Option<&'all mut PlayerStats> {
+    let stats = game.players.get(&id).and_then(|name| all.get_mut(name));
+    if stats.is_none() {
+        log::error!("Bad game report: player {} ({}) was not found", id, role);
+    }
+    stats
+}
@@ -0,0 +1,179 @@
+//! This module exposes streaming of the log report as JSON: each game report
+//! is written as soon as its game is parsed, instead of collecting all game
+//! reports in memory first. Only the sections aggregated across games are
+//! kept, and they are written at the end.
+
+use super::{LogReportBuilder, ReportConfig};
+use crate::{error::Result, game::Game};
+use serde::Serialize;
+use std::io::Write;
+
+#[cfg(test)]
+mod test;
+
+/// Indentation of a single level of pretty JSON, as written by `serde_json`.
+const INDENT: &[u8] = b"  ";
+
+/// Writer of the log report as a JSON object, game by game. The output is the
+/// same as serializing the whole `LogReport`.
+#[derive(Debug)]
+pub struct JsonReportWriter<'config, W> {
+    /// Where the JSON is written to.
+    output: JsonOutput<W>,
+    /// Whether any game was written.
+    has_games: bool,
+    /// Aggregates sections across games, without keeping game reports.
+    builder: LogReportBuilder<'config>,
+}
+
+impl<'config, W> JsonReportWriter<'config, W>
+where
+    W: Write,
+{
+    /// Starts writing indented JSON into the given writer, using the given
+    /// report configuration.
+    pub fn pretty(writer: W, config: &'config ReportConfig) -> Result<Self> {
+        Self::new(writer, config, true)
+    }
+
+    /// Starts writing JSON without whitespace into the given writer, using
+    /// the given report configuration.
+    pub fn compact(writer: W, config: &'config ReportConfig) -> Result<Self> {
+        Self::new(writer, config, false)
+    }
+
+    /// Starts writing the report, opening the dictionary of games.
+    fn new(
+        writer: W,
+        config: &'config ReportConfig,
+        pretty: bool,
+    ) -> Result<Self> {
+        let mut output = JsonOutput { writer, pretty };
+        output.writer.write_all(b"{")?;
+        output.write_key("games", 1)?;
+        output.writer.write_all(b"{")?;
+        Ok(Self {
+            output,
+            has_games: false,
+            builder: LogReportBuilder::new(config),
+        })
+    }
+
+    /// Reports the given game and writes its report right away.
+    pub fn add_game(&mut self, game: &Game) -> Result<()> {
+        let (game_id, game_report) = self.builder.report_game(game)?;
+        if self.has_games {
+            self.output.writer.write_all(b",")?;
+        }
+        self.has_games = true;
+        self.output.write_key(&game_id, 2)?;
+        self.output.write_value(&game_report, 2)?;
+        Ok(())
+    }
+
+    /// Reports and writes every game yielded by the given iterator.
+    pub fn add_games<I>(&mut self, game_iter: I) -> Result<()>
+    where
+        I: IntoIterator<Item = Result<Game>>,
+    {
+        for result in game_iter {
+            self.add_game(&result?)?;
+        }
+        Ok(())
+    }
+
+    /// Closes the dictionary of games, writes the sections aggregated across
+    /// games and a final line feed, and returns the inner writer.
+    pub fn finish(self) -> Result<W> {
+        let mut output = self.output;
+        if self.has_games {
+            output.write_line_break(1)?;
+        }
+        output.writer.write_all(b"}")?;
+
+        let report = self.builder.finish();
+        output.write_fields(&report.aggregates)?;
+        output.writer.write_all(b"\n")?;
+        output.writer.flush()?;
+        Ok(output.writer)
+    }
+}
+
+/// JSON output of the report, written piece by piece.
+#[derive(Debug)]
+struct JsonOutput<W> {
+    /// Where the JSON is written to.
+    writer: W,
+    /// Whether the JSON is indented.
+    pretty: bool,
+}
+
+impl<W> JsonOutput<W>
+where
+    W: Write,
+{
+    /// Writes the fields of the given object into the top-level object,
+    /// after a previous field, and closes the top-level object. The given
+    /// object must have at least one field.
+    fn write_fields<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        let serialized = if self.pretty {
+            serde_json::to_vec_pretty(value)?
+        } else {
+            serde_json::to_vec(value)?
+        };
+        // The fields are at the same depth as the fields of the top-level
+        // object, so replacing the opening brace of the given object with a
+        // comma continues the top-level object.
+        self.writer.write_all(b",")?;
+        self.writer.write_all(&serialized[1 ..])?;
+        Ok(())
+    }
+
+    /// Writes an object key at the given nesting depth, followed by the
+    /// separator from its value.
+    fn write_key(&mut self, key: &str, depth: usize) -> Result<()> {
+        self.write_line_break(depth)?;
+        serde_json::to_writer(&mut self.writer, key)?;
+        let separator: &[u8] = if self.pretty { b": " } else { b":" };
+        self.writer.write_all(separator)?;
+        Ok(())
+    }
+
+    /// Writes a value whose key is at the given nesting depth. In pretty
+    /// JSON, the lines of the value after the first one are indented by that
+    /// depth.
+    fn write_value<T>(&mut self, value: &T, depth: usize) -> Result<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        if !self.pretty {
+            serde_json::to_writer(&mut self.writer, value)?;
+            return Ok(());
+        }
+        // Line feeds within strings are escaped, so every line feed in the
+        // serialized value is between tokens.
+        let serialized = serde_json::to_vec_pretty(value)?;
+        for (i, line) in serialized.split(|&byte| byte == b'\n').enumerate() {
+            if i > 0 {
+                self.write_line_break(depth)?;
+            }
+            self.writer.write_all(line)?;
+        }
+        Ok(())
+    }
+
+    /// Starts a new line indented by the given nesting depth, if the JSON is
+    /// pretty.
+    fn write_line_break(&mut self, depth: usize) -> Result<()> {
+        if self.pretty {
+            self.writer.write_all(b"\n")?;
+            for _ in 0 .. depth {
+                self.writer.write_all(INDENT)?;
+            }
+        }
+        Ok(())
+    }
+}
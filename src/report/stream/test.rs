@@ -0,0 +1,95 @@
+use super::JsonReportWriter;
+use crate::{
+    game::{Game, Kill, Killer, MeansOfDeath, PlayerName},
+    report::{
+        rating::{EloConfig, RatingSystem},
+        render::Format,
+        LogReport,
+        ReportConfig,
+    },
+};
+use std::collections::HashMap;
+
+fn games() -> Vec<Game> {
+    let solo = Game {
+        players: HashMap::from([(2, PlayerName::from("Isgalamido"))]),
+        ..Game::default()
+    };
+    let duel = Game {
+        players: HashMap::from([
+            (2, PlayerName::from("Dono da \"Bola\"")),
+            (3, PlayerName::from("Isgalamido")),
+        ]),
+        kills: vec![
+            Kill {
+                killer: Killer::World,
+                target: 3,
+                means: MeansOfDeath::from("MOD_TRIGGER_HURT"),
+                time: 2 * 60,
+            },
+            Kill {
+                killer: Killer::Player(2),
+                target: 3,
+                means: MeansOfDeath::from("MOD_ROCKET"),
+                time: 2 * 60 + 11,
+            },
+        ],
+        ..Game::default()
+    };
+    vec![solo, duel]
+}
+
+fn expected(games: &[Game], config: &ReportConfig, format: Format) -> String {
+    let game_iter = games.iter().cloned().map(Ok);
+    let report = LogReport::generate_with(game_iter, config).unwrap();
+    let mut output = Vec::new();
+    format.render(&report, &mut output).unwrap();
+    String::from_utf8(output).unwrap()
+}
+
+fn streamed(games: &[Game], config: &ReportConfig, pretty: bool) -> String {
+    let output = Vec::new();
+    let mut writer = if pretty {
+        JsonReportWriter::pretty(output, config).unwrap()
+    } else {
+        JsonReportWriter::compact(output, config).unwrap()
+    };
+    writer.add_games(games.iter().cloned().map(Ok)).unwrap();
+    String::from_utf8(writer.finish().unwrap()).unwrap()
+}
+
+#[test]
+fn same_output_as_whole_report() {
+    let games = games();
+    let configs = [
+        ReportConfig::default(),
+        ReportConfig {
+            games_before: 3,
+            rating: Some(RatingSystem::Elo(EloConfig::default())),
+            ..ReportConfig::default()
+        },
+    ];
+    for config in &configs {
+        assert_eq!(
+            streamed(&games, config, true),
+            expected(&games, config, Format::JsonPretty),
+        );
+        assert_eq!(
+            streamed(&games, config, false),
+            expected(&games, config, Format::JsonCompact),
+        );
+    }
+}
+
+#[test]
+fn no_games() {
+    let config = ReportConfig::default();
+    assert_eq!(
+        streamed(&[], &config, true),
+        expected(&[], &config, Format::JsonPretty),
+    );
+    assert_eq!(
+        streamed(&[], &config, false),
+        expected(&[], &config, Format::JsonCompact),
+    );
+}
@@ -1,11 +1,23 @@
-use super::GameReport;
+use super::{
+    highlights::{FirstBlood, Highlights},
+    matrix::KillMatrix,
+    ranking::{RankEntry, Ranking},
+    GameReport,
+    LogReport,
+    ReportConfig,
+};
 use crate::game::{
-    all_means_of_death,
+    Award,
+    AwardCount,
     Game,
+    GameType,
+    GainedAward,
     Kill,
     Killer,
     MeansOfDeath,
     PlayerName,
+    Weapon,
+    WeaponCategory,
 };
 use indexmap::{IndexMap, IndexSet};
 use std::collections::HashMap;
@@ -14,6 +26,7 @@ fn game_1() -> Game {
     Game {
         players: HashMap::from([(2, PlayerName::from("Isgalamido"))]),
         kills: Vec::new(),
+        ..Game::default()
     }
 }
 
@@ -29,41 +42,95 @@ fn game_2() -> Game {
                 killer: Killer::World,
                 target: 3,
                 means: MeansOfDeath::from("MOD_TRIGGER_HURT"),
+                time: 2 * 60,
             },
             Kill {
                 killer: Killer::World,
                 target: 2,
                 means: MeansOfDeath::from("MOD_FALLING"),
+                time: 2 * 60 + 4,
             },
             Kill {
                 killer: Killer::World,
                 target: 3,
                 means: MeansOfDeath::from("MOD_FALLING"),
+                time: 2 * 60 + 4,
             },
             Kill {
                 killer: Killer::Player(2),
                 target: 4,
                 means: MeansOfDeath::from("MOD_ROCKET"),
+                time: 2 * 60 + 11,
             },
         ],
+        awards: HashMap::from([(
+            3,
+            vec![GainedAward { award: Award::Excellent, time: 2 * 60 + 1 }],
+        )]),
+        ..Game::default()
     }
 }
 
+fn no_awards() -> IndexMap<Award, AwardCount> {
+    Award::ALL.iter().map(|&award| (award, 0)).collect()
+}
+
 fn game_report_1() -> GameReport {
     GameReport {
+        game_type: GameType::FreeForAll,
+        map: None,
+        duration: 0,
         total_kills: 0,
         players: IndexSet::from([PlayerName::from("Isgalamido")]),
         kills: IndexMap::from([(PlayerName::from("Isgalamido"), 0)]),
-        kills_by_means: all_means_of_death()
+        kills_by_means: MeansOfDeath::ALL
+            .iter()
+            .cloned()
+            .map(|means| (means, 0))
+            .collect(),
+        kills_by_weapon: Weapon::ALL
+            .iter()
+            .map(|&weapon| (weapon, 0))
+            .collect(),
+        kills_by_category: WeaponCategory::ALL
             .iter()
-            .copied()
-            .map(|means| (MeansOfDeath::from(means), 0))
+            .map(|&category| (category, 0))
             .collect(),
+        kill_matrix: {
+            let mut matrix = KillMatrix::new();
+            matrix.insert_player("Isgalamido");
+            matrix
+        },
+        ranking: Ranking {
+            entries: vec![RankEntry {
+                position: 1,
+                player: PlayerName::from("Isgalamido"),
+                score: 0,
+                frags: 0,
+                deaths: 0,
+                kill_death_ratio: 0.0,
+            }],
+        },
+        teams: IndexMap::new(),
+        team_scores: IndexMap::new(),
+        highlights: Highlights {
+            longest_streaks: IndexMap::from([(
+                PlayerName::from("Isgalamido"),
+                0,
+            )]),
+            ..Highlights::default()
+        },
+        awards: IndexMap::from([(PlayerName::from("Isgalamido"), no_awards())]),
+        ctf: None,
+        timeline: None,
     }
 }
 
 fn game_report_2() -> GameReport {
     GameReport {
+        game_type: GameType::FreeForAll,
+        map: None,
+        duration: 2 * 60 + 11,
         total_kills: 4,
         players: IndexSet::from([
             PlayerName::from("Dono da Bola"),
@@ -75,19 +142,103 @@ fn game_report_2() -> GameReport {
             (PlayerName::from("Isgalamido"), -2),
             (PlayerName::from("Zeh"), 0),
         ]),
-        kills_by_means: all_means_of_death()
+        kills_by_means: MeansOfDeath::ALL
             .iter()
-            .copied()
+            .cloned()
             .map(|means| {
                 let count = match means {
-                    "MOD_TRIGGER_HURT" => 1,
-                    "MOD_FALLING" => 2,
-                    "MOD_ROCKET" => 1,
+                    MeansOfDeath::TriggerHurt => 1,
+                    MeansOfDeath::Falling => 2,
+                    MeansOfDeath::Rocket => 1,
+                    _ => 0,
+                };
+                (means, count)
+            })
+            .collect(),
+        kills_by_weapon: Weapon::ALL
+            .iter()
+            .map(|&weapon| {
+                let count = match weapon {
+                    Weapon::RocketLauncher => 1,
+                    Weapon::Other => 3,
                     _ => 0,
                 };
-                (MeansOfDeath::from(means), count)
+                (weapon, count)
             })
             .collect(),
+        kills_by_category: WeaponCategory::ALL
+            .iter()
+            .map(|&category| {
+                let count = match category {
+                    WeaponCategory::Projectile => 1,
+                    WeaponCategory::Environmental => 3,
+                    _ => 0,
+                };
+                (category, count)
+            })
+            .collect(),
+        kill_matrix: {
+            let mut matrix = KillMatrix::new();
+            matrix.insert_player("Isgalamido");
+            matrix.insert_player("Zeh");
+            matrix.add("Dono da Bola", "Zeh", 1);
+            matrix
+        },
+        ranking: Ranking {
+            entries: vec![
+                RankEntry {
+                    position: 1,
+                    player: PlayerName::from("Dono da Bola"),
+                    score: 0,
+                    frags: 1,
+                    deaths: 1,
+                    kill_death_ratio: 1.0,
+                },
+                RankEntry {
+                    position: 1,
+                    player: PlayerName::from("Zeh"),
+                    score: 0,
+                    frags: 0,
+                    deaths: 1,
+                    kill_death_ratio: 0.0,
+                },
+                RankEntry {
+                    position: 3,
+                    player: PlayerName::from("Isgalamido"),
+                    score: -2,
+                    frags: 0,
+                    deaths: 2,
+                    kill_death_ratio: 0.0,
+                },
+            ],
+        },
+        teams: IndexMap::new(),
+        team_scores: IndexMap::new(),
+        highlights: Highlights {
+            first_blood: Some(FirstBlood {
+                killer: PlayerName::from("Dono da Bola"),
+                target: PlayerName::from("Zeh"),
+                time: 2 * 60 + 11,
+            }),
+            longest_streaks: IndexMap::from([
+                (PlayerName::from("Dono da Bola"), 1),
+                (PlayerName::from("Isgalamido"), 0),
+                (PlayerName::from("Zeh"), 0),
+            ]),
+            multi_kills: Vec::new(),
+            shutdowns: Vec::new(),
+        },
+        awards: IndexMap::from([
+            (PlayerName::from("Dono da Bola"), no_awards()),
+            (PlayerName::from("Isgalamido"), {
+                let mut awards = no_awards();
+                awards[&Award::Excellent] = 1;
+                awards
+            }),
+            (PlayerName::from("Zeh"), no_awards()),
+        ]),
+        ctf: None,
+        timeline: None,
     }
 }
 
@@ -104,3 +255,47 @@ fn generate_game_report_2() {
     let actual = GameReport::generate(&game_2()).unwrap();
     assert_eq!(expected, actual);
 }
+
+#[test]
+fn generate_log_report_kill_matrix() {
+    let report = LogReport::generate([Ok(game_1()), Ok(game_2())]).unwrap();
+    let matrix = &report.aggregates.kill_matrix;
+    assert_eq!(matrix.players().count(), 3);
+    assert_eq!(matrix.get("Dono da Bola", "Zeh"), 1);
+    assert_eq!(matrix.get("Zeh", "Dono da Bola"), 0);
+    assert_eq!(matrix.get("Isgalamido", "Isgalamido"), 0);
+}
+
+#[test]
+fn generate_log_report_after_previous_games() {
+    let config = ReportConfig { games_before: 5, ..ReportConfig::default() };
+    let games = [Ok(game_1()), Ok(game_2())];
+    let report = LogReport::generate_with(games, &config).unwrap();
+    let game_ids: Vec<_> = report.games.keys().collect();
+    assert_eq!(game_ids, ["game_6", "game_7"]);
+    assert_eq!(report.games["game_7"], game_report_2());
+}
+
+#[test]
+fn generate_game_report_ctf_only_in_ctf_games() {
+    let game = Game { game_type: GameType::CaptureTheFlag, ..game_2() };
+    let report = GameReport::generate(&game).unwrap();
+    let ctf = report.ctf.unwrap();
+    assert_eq!(ctf.players.len(), 3);
+    assert!(ctf.possessions.is_empty());
+
+    let report = GameReport::generate(&game_2()).unwrap();
+    assert_eq!(report.ctf, None);
+}
+
+#[test]
+fn generate_game_report_unknown_means() {
+    let mut game = game_2();
+    game.kills[0].means = MeansOfDeath::from("MOD_SWORD");
+    let report = GameReport::generate(&game).unwrap();
+    assert_eq!(report.kills_by_means.len(), MeansOfDeath::ALL.len() + 1);
+    assert_eq!(report.kills_by_means[&MeansOfDeath::TriggerHurt], 0);
+    let (last_means, count) = report.kills_by_means.last().unwrap();
+    assert_eq!(last_means.name(), "MOD_SWORD");
+    assert_eq!(*count, 1);
+}
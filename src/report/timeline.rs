@@ -0,0 +1,222 @@
+//! This module exposes the score-over-time timeline of a game, along with
+//! counts of lead changes and comebacks.
+
+use super::stats::PlayerStats;
+use crate::game::{Game, KillCount, PlayerName, Team, Timestamp};
+use indexmap::IndexMap;
+use std::{collections::HashMap, hash::Hash};
+
+#[cfg(test)]
+mod test;
+
+/// Count of changes in a timeline, e.g. lead changes.
+pub type ChangeCount = u64;
+
+/// When scores are sampled into the timeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TimelineSampling {
+    /// After every kill.
+    #[default]
+    EveryKill,
+    /// Every given number of seconds since the start of the game.
+    Interval(Timestamp),
+}
+
+/// Configuration of score timelines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimelineConfig {
+    /// When scores are sampled.
+    pub sampling: TimelineSampling,
+    /// Minimum number of points a player (or team) must have been behind the
+    /// leader for taking the lead to be accounted as a comeback.
+    pub comeback_deficit: KillCount,
+}
+
+impl Default for TimelineConfig {
+    fn default() -> Self {
+        Self { sampling: TimelineSampling::default(), comeback_deficit: 3 }
+    }
+}
+
+/// Scores at some point of a game.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct TimelinePoint {
+    /// When the scores were sampled.
+    pub time: Timestamp,
+    /// The mapping of player names to their cumulative scores.
+    pub scores: IndexMap<PlayerName, KillCount>,
+    /// The mapping of teams to their cumulative scores. Only filled in team
+    /// games.
+    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    pub team_scores: IndexMap<Team, KillCount>,
+}
+
+/// Scores over time of a single game. The lead is held by teams in team
+/// games and by players otherwise, and nobody holds it while the top score
+/// is tied.
+#[derive(Debug, Clone, PartialEq, Eq, Default, serde::Serialize)]
+pub struct ScoreTimeline {
+    /// Sampled scores in time order, starting with everyone at `0` when the
+    /// game starts.
+    pub points: Vec<TimelinePoint>,
+    /// Times the lead passed from one player (or team) to another.
+    pub lead_changes: ChangeCount,
+    /// Times a player (or team) took the lead after being behind by at least
+    /// the configured deficit since they last led.
+    pub comebacks: ChangeCount,
+}
+
+impl ScoreTimeline {
+    /// Generates the score timeline of the given game. Lead changes and
+    /// comebacks are checked after every kill, regardless of sampling. Team
+    /// scores use the teams players were last in.
+    pub fn generate(game: &Game, config: &TimelineConfig) -> Self {
+        let team_game = game.game_type.is_team_game();
+        let teams: HashMap<_, _> = if team_game {
+            game.players
+                .iter()
+                .filter_map(|(id, name)| Some((name, *game.teams.get(id)?)))
+                .collect()
+        } else {
+            HashMap::new()
+        };
+
+        let mut this = Self::default();
+        let mut stats: IndexMap<_, _> = game
+            .players
+            .values()
+            .cloned()
+            .map(|name| (name, PlayerStats::default()))
+            .collect();
+        let mut player_lead = LeadTracker::default();
+        let mut team_lead = LeadTracker::default();
+
+        let sample = |time, stats: &IndexMap<PlayerName, PlayerStats>| {
+            let scores: IndexMap<_, _> = stats
+                .iter()
+                .map(|(name, player_stats)| (name.clone(), player_stats.score))
+                .collect();
+            let mut team_scores = IndexMap::new();
+            if team_game {
+                team_scores.insert(Team::Red, 0);
+                team_scores.insert(Team::Blue, 0);
+                for (name, score) in &scores {
+                    if let Some(team_score) = teams
+                        .get(name)
+                        .and_then(|team| team_scores.get_mut(team))
+                    {
+                        *team_score += score;
+                    }
+                }
+            }
+            TimelinePoint { time, scores, team_scores }
+        };
+
+        this.points.push(sample(game.start_time, &stats));
+        let mut next_sample_time = match config.sampling {
+            TimelineSampling::EveryKill => None,
+            TimelineSampling::Interval(interval) => {
+                Some((interval.max(1), game.start_time + interval.max(1)))
+            },
+        };
+
+        for kill in &game.kills {
+            if let Some((interval, next_time)) = &mut next_sample_time {
+                while kill.time > *next_time {
+                    this.points.push(sample(*next_time, &stats));
+                    *next_time += *interval;
+                }
+            }
+
+            PlayerStats::add_kill(game, &mut stats, kill);
+            let point = sample(kill.time, &stats);
+            if team_game {
+                team_lead.update(&point.team_scores, config.comeback_deficit);
+            } else {
+                player_lead.update(&point.scores, config.comeback_deficit);
+            }
+            if next_sample_time.is_none() {
+                this.points.push(point);
+            }
+        }
+
+        let end_time = game.kills.last().map_or(game.start_time, |kill| {
+            kill.time.max(game.start_time)
+        });
+        if let Some((interval, mut next_time)) = next_sample_time {
+            while next_time <= end_time {
+                this.points.push(sample(next_time, &stats));
+                next_time += interval;
+            }
+            if this.points.last().is_some_and(|point| point.time < end_time) {
+                this.points.push(sample(end_time, &stats));
+            }
+        }
+
+        this.lead_changes = player_lead.lead_changes + team_lead.lead_changes;
+        this.comebacks = player_lead.comebacks + team_lead.comebacks;
+        this
+    }
+}
+
+/// Tracks who holds the lead among some competitors, players or teams.
+#[derive(Debug, Clone)]
+struct LeadTracker<K> {
+    /// The competitor who last held the lead alone, if any.
+    leader: Option<K>,
+    /// The mapping of competitors to the largest deficit they had since they
+    /// last held the lead.
+    max_deficits: HashMap<K, KillCount>,
+    /// Times the lead passed from one competitor to another.
+    lead_changes: ChangeCount,
+    /// Times a competitor took the lead after a large enough deficit.
+    comebacks: ChangeCount,
+}
+
+impl<K> Default for LeadTracker<K> {
+    fn default() -> Self {
+        Self {
+            leader: None,
+            max_deficits: HashMap::new(),
+            lead_changes: 0,
+            comebacks: 0,
+        }
+    }
+}
+
+impl<K> LeadTracker<K>
+where
+    K: Clone + Eq + Hash,
+{
+    /// Updates the lead given the current scores of the competitors.
+    fn update(
+        &mut self,
+        scores: &IndexMap<K, KillCount>,
+        comeback_deficit: KillCount,
+    ) {
+        let Some(top_score) = scores.values().copied().max() else {
+            return;
+        };
+        for (competitor, score) in scores {
+            let max_deficit =
+                self.max_deficits.entry(competitor.clone()).or_default();
+            *max_deficit = (*max_deficit).max(top_score - score);
+        }
+
+        let mut leaders =
+            scores.iter().filter(|(_, score)| **score == top_score);
+        let (Some((leader, _)), None) = (leaders.next(), leaders.next()) else {
+            return;
+        };
+        if self.leader.as_ref() != Some(leader) {
+            if self.leader.is_some() {
+                self.lead_changes += 1;
+            }
+            if self.max_deficits[leader] >= comeback_deficit {
+                self.comebacks += 1;
+            }
+            self.leader = Some(leader.clone());
+        }
+        self.max_deficits.insert(leader.clone(), 0);
+    }
+}
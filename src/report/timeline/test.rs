@@ -0,0 +1,95 @@
+use super::{ScoreTimeline, TimelineConfig, TimelineSampling};
+use crate::game::{
+    Game,
+    GameType,
+    Kill,
+    Killer,
+    MeansOfDeath,
+    PlayerName,
+    Team,
+    Timestamp,
+};
+use indexmap::IndexMap;
+use std::collections::HashMap;
+
+fn kill(killer: Killer, target: u32, time: Timestamp) -> Kill {
+    Kill { killer, target, means: MeansOfDeath::from("MOD_ROCKET"), time }
+}
+
+fn game() -> Game {
+    Game {
+        start_time: 100,
+        players: HashMap::from([
+            (2, PlayerName::from("Zeh")),
+            (3, PlayerName::from("Mal")),
+            (4, PlayerName::from("Isgalamido")),
+        ]),
+        kills: vec![
+            kill(Killer::Player(2), 3, 101),
+            kill(Killer::Player(2), 4, 102),
+            kill(Killer::Player(2), 3, 103),
+            kill(Killer::Player(4), 2, 110),
+            kill(Killer::Player(4), 3, 111),
+            kill(Killer::Player(4), 2, 112),
+            kill(Killer::Player(4), 3, 120),
+            kill(Killer::World, 4, 121),
+        ],
+        ..Game::default()
+    }
+}
+
+#[test]
+fn every_kill() {
+    let timeline = ScoreTimeline::generate(&game(), &TimelineConfig::default());
+    let times: Vec<_> =
+        timeline.points.iter().map(|point| point.time).collect();
+    assert_eq!(times, [100, 101, 102, 103, 110, 111, 112, 120, 121]);
+    assert!(timeline.points[0].scores.values().all(|&score| score == 0));
+    assert_eq!(timeline.points[3].scores["Zeh"], 3);
+    let last = timeline.points.last().unwrap();
+    assert_eq!(last.scores["Zeh"], 3);
+    assert_eq!(last.scores["Isgalamido"], 3);
+    assert!(last.team_scores.is_empty());
+}
+
+#[test]
+fn fixed_interval() {
+    let config = TimelineConfig {
+        sampling: TimelineSampling::Interval(5),
+        ..TimelineConfig::default()
+    };
+    let timeline = ScoreTimeline::generate(&game(), &config);
+    let samples: Vec<_> = timeline
+        .points
+        .iter()
+        .map(|point| (point.time, point.scores["Isgalamido"]))
+        .collect();
+    let expected = [(100, 0), (105, 0), (110, 1), (115, 3), (120, 4), (121, 3)];
+    assert_eq!(expected, samples.as_slice());
+}
+
+#[test]
+fn lead_changes_and_comebacks() {
+    let timeline = ScoreTimeline::generate(&game(), &TimelineConfig::default());
+    assert_eq!(timeline.lead_changes, 1);
+    assert_eq!(timeline.comebacks, 1);
+
+    let config = TimelineConfig { comeback_deficit: 4, ..Default::default() };
+    let timeline = ScoreTimeline::generate(&game(), &config);
+    assert_eq!(timeline.lead_changes, 1);
+    assert_eq!(timeline.comebacks, 0);
+}
+
+#[test]
+fn team_lead() {
+    let game = Game {
+        game_type: GameType::TeamDeathmatch,
+        teams: HashMap::from([(2, Team::Red), (3, Team::Red), (4, Team::Blue)]),
+        ..game()
+    };
+    let timeline = ScoreTimeline::generate(&game, &TimelineConfig::default());
+    let expected = IndexMap::from([(Team::Red, 3), (Team::Blue, 3)]);
+    assert_eq!(expected, timeline.points.last().unwrap().team_scores);
+    assert_eq!(timeline.lead_changes, 1);
+    assert_eq!(timeline.comebacks, 1);
+}
@@ -0,0 +1,189 @@
+//! This module exposes player statistics aggregated across all games of a log,
+//! such as games played, wins and the best game of each player.
+
+use super::{stats::PlayerStats, GameCount, GameName};
+use crate::game::{strip_color_codes, KillCount, PlayerName};
+use indexmap::IndexMap;
+use std::collections::HashMap;
+
+#[cfg(test)]
+mod test;
+
+/// How a player is identified across games, since the log has no stable
+/// player identity besides the name.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum IdentityKey {
+    /// The exact name, including color codes.
+    #[default]
+    Name,
+    /// The name without color codes, e.g. `^1Zeh` and `Zeh` are the same
+    /// player.
+    StrippedName,
+    /// Groups of aliases of the same player, matched without color codes.
+    Alias(AliasGroups),
+}
+
+impl IdentityKey {
+    /// Returns the identity of the player with the given name.
+    pub fn identify(&self, name: &str) -> PlayerName {
+        match self {
+            Self::Name => PlayerName::from(name),
+            Self::StrippedName => strip_color_codes(name),
+            Self::Alias(groups) => groups.resolve(name),
+        }
+    }
+}
+
+/// Groups of names known to belong to the same player. Each group has a
+/// canonical name, which is used as the player's identity.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct AliasGroups {
+    /// Mapping of stripped aliases to their canonical names.
+    canonical: HashMap<PlayerName, PlayerName>,
+}
+
+impl AliasGroups {
+    /// Creates an empty set of groups, where every name is its own player.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Inserts a group of aliases with the given canonical name. The canonical
+    /// name is implicitly an alias of itself.
+    pub fn insert_group<I, S>(&mut self, canonical: &str, aliases: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let canonical_name = PlayerName::from(canonical);
+        self.canonical
+            .insert(strip_color_codes(canonical), canonical_name.clone());
+        for alias in aliases {
+            self.canonical.insert(
+                strip_color_codes(alias.as_ref()),
+                canonical_name.clone(),
+            );
+        }
+    }
+
+    /// Resolves a name to its canonical name. Names without a group resolve
+    /// to themselves, without color codes.
+    pub fn resolve(&self, name: &str) -> PlayerName {
+        let stripped = strip_color_codes(name);
+        match self.canonical.get(&stripped) {
+            Some(canonical) => canonical.clone(),
+            None => stripped,
+        }
+    }
+}
+
+/// The game in which a player had their highest score.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct BestGame {
+    /// Name of the game in the log report.
+    pub game: GameName,
+    /// The player's score in that game.
+    pub score: KillCount,
+}
+
+/// Statistics of a single player aggregated across games.
+#[derive(Debug, Clone, PartialEq, Default, serde::Serialize)]
+pub struct PlayerTotal {
+    /// How many games the player was in.
+    pub games_played: GameCount,
+    /// Kills of other players, summed across games.
+    pub frags: KillCount,
+    /// Deaths, summed across games.
+    pub deaths: KillCount,
+    /// How many games the player finished with the top score (possibly tied).
+    /// Games without kills have no winner.
+    pub wins: GameCount,
+    /// Scores summed across games.
+    pub total_score: KillCount,
+    /// Average score per game played.
+    pub average_score: f64,
+    /// The game with the player's highest score, the earliest one if tied.
+    pub best_game: Option<BestGame>,
+}
+
+/// Statistics of every player aggregated across the games of a log, indexed by
+/// player identity.
+#[derive(Debug, Clone, PartialEq, Default, serde::Serialize)]
+#[serde(transparent)]
+pub struct PlayerTotals {
+    /// How players are identified across games.
+    #[serde(skip)]
+    identity: IdentityKey,
+    /// Totals indexed by player identity, in order of first appearance.
+    pub players: IndexMap<PlayerName, PlayerTotal>,
+}
+
+impl PlayerTotals {
+    /// Creates empty totals, identifying players with the given key.
+    pub fn new(identity: IdentityKey) -> Self {
+        Self { identity, players: IndexMap::new() }
+    }
+
+    /// The key used to identify players across games.
+    pub fn identity(&self) -> &IdentityKey {
+        &self.identity
+    }
+
+    /// Statistics of each player summed across games, e.g. for ranking them.
+    pub fn stats(&self) -> IndexMap<PlayerName, PlayerStats> {
+        self.players
+            .iter()
+            .map(|(identity, total)| {
+                let stats = PlayerStats {
+                    score: total.total_score,
+                    frags: total.frags,
+                    deaths: total.deaths,
+                };
+                (identity.clone(), stats)
+            })
+            .collect()
+    }
+
+    /// Accounts a game, given its name and its per-player statistics. Players
+    /// sharing the same identity in a single game are accounted together.
+    pub fn add_game(
+        &mut self,
+        game_name: &str,
+        stats: &IndexMap<PlayerName, PlayerStats>,
+    ) {
+        let mut merged = IndexMap::<PlayerName, PlayerStats>::new();
+        for (name, player_stats) in stats {
+            let entry =
+                merged.entry(self.identity.identify(name)).or_default();
+            entry.score += player_stats.score;
+            entry.frags += player_stats.frags;
+            entry.deaths += player_stats.deaths;
+        }
+
+        let has_kills = merged.values().any(|stats| stats.deaths > 0);
+        let top_score = merged.values().map(|stats| stats.score).max();
+
+        for (identity, player_stats) in merged {
+            let total = self.players.entry(identity).or_default();
+            total.games_played += 1;
+            total.frags += player_stats.frags;
+            total.deaths += player_stats.deaths;
+            total.total_score += player_stats.score;
+            total.average_score =
+                total.total_score as f64 / total.games_played as f64;
+            if has_kills && Some(player_stats.score) == top_score {
+                total.wins += 1;
+            }
+            let is_best = total
+                .best_game
+                .as_ref()
+                .is_none_or(|best| player_stats.score > best.score);
+            if is_best {
+                total.best_game = Some(BestGame {
+                    game: GameName::from(game_name),
+                    score: player_stats.score,
+                });
+            }
+        }
+    }
+}
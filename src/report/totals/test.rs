@@ -0,0 +1,69 @@
+use super::{AliasGroups, BestGame, IdentityKey, PlayerTotals};
+use crate::{game::PlayerName, report::stats::PlayerStats};
+use indexmap::IndexMap;
+
+fn stats(
+    entries: &[(&str, i64, i64, i64)],
+) -> IndexMap<PlayerName, PlayerStats> {
+    entries
+        .iter()
+        .map(|&(name, score, frags, deaths)| {
+            (PlayerName::from(name), PlayerStats { score, frags, deaths })
+        })
+        .collect()
+}
+
+#[test]
+fn add_games_by_name() {
+    let mut totals = PlayerTotals::new(IdentityKey::Name);
+    totals.add_game("game_1", &stats(&[("Zeh", 3, 3, 1), ("Mal", 1, 2, 3)]));
+    totals.add_game("game_2", &stats(&[("Zeh", 5, 5, 0), ("^1Mal", 5, 5, 2)]));
+    totals.add_game("game_3", &stats(&[("Zeh", 0, 0, 0)]));
+
+    let zeh = &totals.players["Zeh"];
+    assert_eq!(zeh.games_played, 3);
+    assert_eq!(zeh.frags, 8);
+    assert_eq!(zeh.deaths, 1);
+    assert_eq!(zeh.wins, 2);
+    assert_eq!(zeh.total_score, 8);
+    assert!((zeh.average_score - 8.0 / 3.0).abs() < 1e-9);
+    assert_eq!(
+        zeh.best_game,
+        Some(BestGame { game: String::from("game_2"), score: 5 })
+    );
+
+    assert_eq!(totals.players["Mal"].games_played, 1);
+    assert_eq!(totals.players["Mal"].wins, 0);
+    assert_eq!(totals.players["^1Mal"].wins, 1);
+}
+
+#[test]
+fn add_games_by_stripped_name() {
+    let mut totals = PlayerTotals::new(IdentityKey::StrippedName);
+    totals.add_game("game_1", &stats(&[("Mal", 1, 2, 3)]));
+    totals.add_game("game_2", &stats(&[("^1Mal", 5, 5, 2)]));
+
+    assert_eq!(totals.players.len(), 1);
+    let mal = &totals.players["Mal"];
+    assert_eq!(mal.games_played, 2);
+    assert_eq!(mal.deaths, 5);
+    assert_eq!(mal.best_game.as_ref().unwrap().game, "game_2");
+}
+
+#[test]
+fn add_games_by_alias() {
+    let mut groups = AliasGroups::new();
+    groups.insert_group("Isgalamido", ["Isga", "^2Isga^7lamido"]);
+    let mut totals = PlayerTotals::new(IdentityKey::Alias(groups));
+    totals.add_game("game_1", &stats(&[("Isga", 2, 2, 0), ("Zeh", 0, 0, 2)]));
+    totals.add_game(
+        "game_2",
+        &stats(&[("^2Isga^7lamido", 1, 1, 0), ("Isgalamido", 1, 1, 1)]),
+    );
+
+    let isgalamido = &totals.players["Isgalamido"];
+    assert_eq!(isgalamido.games_played, 2);
+    assert_eq!(isgalamido.total_score, 4);
+    assert_eq!(isgalamido.wins, 2);
+    assert_eq!(totals.players["Zeh"].games_played, 1);
+}